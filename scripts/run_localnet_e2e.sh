@@ -70,7 +70,9 @@ DEPLOY_OUTPUT=$(stellar contract deploy \
   --source "$SOURCE_ACCOUNT" \
   --network "$NETWORK_NAME" \
   -- \
-  --vk_bytes-file-path "$DATASET_DIR/vk")
+  --admin "$(stellar keys address "$SOURCE_ACCOUNT")" \
+  --vk_bytes-file-path "$DATASET_DIR/vk" \
+  --circuit_vks '[]')
 echo "$DEPLOY_OUTPUT"
 CONTRACT_ID=$(echo "$DEPLOY_OUTPUT" | tail -n 1 | tr -d '[:space:]')
 if [[ -z "$CONTRACT_ID" ]]; then
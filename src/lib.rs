@@ -1,6 +1,18 @@
 #![no_std]
-use soroban_sdk::{contract, contracterror, contractimpl, symbol_short, Bytes, Env, Symbol};
-use ultrahonk_soroban_verifier::{UltraHonkVerifier, PROOF_BYTES};
+extern crate alloc;
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, vec, Address, Bytes, BytesN,
+    Env, IntoVal, InvokeError, String, Symbol,
+};
+use ultrahonk_soroban_verifier::{
+    shplemini::ShpleminiError,
+    utils::{
+        load_vk_from_bytes, zero_rle_decompress, ProofEnvelope, ENVELOPE_HEADER_BYTES, VK_BYTES,
+    },
+    verifier::VerifyError,
+    UltraHonkVerifier, PROOF_BYTES,
+};
 
 /// Contract
 #[contract]
@@ -14,38 +26,732 @@ pub enum Error {
     ProofParseError = 2,
     VerificationFailed = 3,
     VkNotSet = 4,
+    VkVersionMismatch = 5,
+    AdminNotSet = 6,
+    KeyAlreadyConsumed = 7,
+    InvalidInput = 8,
+    PairingPending = 9,
+    PairingOracleNotSet = 10,
+    PairingNotFound = 11,
+    DuplicateVk = 12,
+    VkHashMismatch = 13,
+    MigrationProofInvalid = 14,
+    MalformedProof = 15,
+    KeyMismatch = 16,
+}
+
+/// Outcome of `inspect_proof`: how far verification got and why it stopped.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VerifyReport {
+    pub vk_parsed: bool,
+    /// Proof length, public inputs and the transcript checked out.
+    pub inputs_valid: bool,
+    pub sumcheck_passed: bool,
+    /// Shplemini and the final pairing passed, i.e. the proof verified.
+    pub pairing_passed: bool,
+    /// `VerifyError::code()` of the first failure; 0 if the proof verified.
+    pub error_code: u32,
+    /// The verifier's message for the first failure; empty if none.
+    pub detail: String,
+}
+
+/// What a deferred pairing was checked against. `pairing_result` only says
+/// the pairing held; a consumer compares this with the VK and public inputs
+/// it expects.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PairingBinding {
+    /// `vk_id(vk_bytes)` of the caller-supplied VK.
+    pub vk_id: BytesN<32>,
+    /// `keccak256(public_inputs)`.
+    pub public_inputs_hash: BytesN<32>,
+    /// Who submitted the proof and paid for the entry.
+    pub submitter: Address,
+}
+
+/// A deferred pairing awaiting the oracle: the two G1 points and what they
+/// were derived from.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingPairing {
+    pub binding: PairingBinding,
+    pub p0: BytesN<64>,
+    pub p1: BytesN<64>,
+}
+
+/// Map a verifier failure: malformed inputs and proofs the opening rejects
+/// as malformed get their own errors, so callers can tell them from a
+/// well-formed proof that is wrong, which stays `VerificationFailed`.
+fn error_from_verify(err: &VerifyError) -> Error {
+    match err {
+        VerifyError::InvalidInput(_) => Error::InvalidInput,
+        VerifyError::ShplonkFailed(e) if *e != ShpleminiError::PairingFailed => {
+            Error::MalformedProof
+        }
+        VerifyError::SumcheckFailed(_) | VerifyError::ShplonkFailed(_) => Error::VerificationFailed,
+    }
 }
 
+/// Ledgers per day at a ~5s close time.
+pub const DAY_IN_LEDGERS: u32 = 17_280;
+/// TTL the instance and the persistent VK entry are extended to.
+pub const TTL_EXTEND_TO: u32 = 30 * DAY_IN_LEDGERS;
+/// Remaining TTL below which an access triggers an extension.
+pub const TTL_THRESHOLD: u32 = TTL_EXTEND_TO - DAY_IN_LEDGERS;
+
 #[contractimpl]
 impl UltraHonkVerifierContract {
     fn key_vk() -> Symbol {
         symbol_short!("vk")
     }
 
-    /// Initialize the on-chain VK once at deploy time.
-    pub fn __constructor(env: Env, vk_bytes: Bytes) -> Result<(), Error> {
-        env.storage().instance().set(&Self::key_vk(), &vk_bytes);
+    fn key_vk_packed() -> Symbol {
+        symbol_short!("vk_rle")
+    }
+
+    fn key_vk_version() -> Symbol {
+        symbol_short!("vk_ver")
+    }
+
+    fn key_admin() -> Symbol {
+        symbol_short!("admin")
+    }
+
+    fn key_consumed(key: &BytesN<32>) -> (Symbol, BytesN<32>) {
+        (symbol_short!("used"), key.clone())
+    }
+
+    fn key_circuit_vk(vk_id: &BytesN<32>) -> (Symbol, BytesN<32>) {
+        (symbol_short!("cvk"), vk_id.clone())
+    }
+
+    fn key_verified(proof_id: &BytesN<32>) -> (Symbol, BytesN<32>) {
+        (symbol_short!("proved"), proof_id.clone())
+    }
+
+    fn key_consumable_index() -> Symbol {
+        symbol_short!("used_idx")
+    }
+
+    fn key_oracle() -> Symbol {
+        symbol_short!("oracle")
+    }
+
+    fn key_next_pending() -> Symbol {
+        symbol_short!("pend_next")
+    }
+
+    fn key_pending(id: u32) -> (Symbol, u32) {
+        (symbol_short!("pend"), id)
+    }
+
+    fn key_paired(id: u32) -> (Symbol, u32) {
+        (symbol_short!("paired"), id)
+    }
+
+    fn require_admin(env: &Env) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&Self::key_admin())
+            .ok_or(Error::AdminNotSet)?;
+        admin.require_auth();
         Ok(())
     }
 
+    /// Keep the instance (admin, version) and the persistent VK entry alive.
+    fn extend_ttls(env: &Env) {
+        env.storage()
+            .instance()
+            .extend_ttl(TTL_THRESHOLD, TTL_EXTEND_TO);
+        let persistent = env.storage().persistent();
+        for key in [Self::key_vk(), Self::key_vk_packed()] {
+            if persistent.has(&key) {
+                persistent.extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+            }
+        }
+    }
+
+    /// The stored VK's bb bytes, whether `set_vk_compressed` stored it
+    /// packed or not.
+    fn stored_vk_bytes(env: &Env) -> Result<Bytes, Error> {
+        let persistent = env.storage().persistent();
+        if let Some(vk_bytes) = persistent.get(&Self::key_vk()) {
+            return Ok(vk_bytes);
+        }
+        let packed: Bytes = persistent
+            .get(&Self::key_vk_packed())
+            .ok_or(Error::VkNotSet)?;
+        Self::unpack_vk(env, &packed)
+    }
+
+    fn unpack_vk(env: &Env, packed: &Bytes) -> Result<Bytes, Error> {
+        let mut buf = alloc::vec![0u8; packed.len() as usize];
+        packed.copy_into_slice(&mut buf);
+        let vk = zero_rle_decompress(&buf, VK_BYTES).map_err(|_| Error::VkParseError)?;
+        Ok(Bytes::from_slice(env, &vk))
+    }
+
+    /// Initialize the admin and the on-chain VK at deploy time.
+    ///
+    /// The VK lives in persistent storage so it is not loaded with the
+    /// instance on every call; admin and version stay in instance storage.
+    /// `circuit_vks` registers further circuits, each under its `vk_id`, for
+    /// `verify_proof_for_circuit`; each must parse and be distinct.
+    pub fn __constructor(
+        env: Env,
+        admin: Address,
+        vk_bytes: Bytes,
+        circuit_vks: soroban_sdk::Vec<Bytes>,
+    ) -> Result<(), Error> {
+        env.storage().instance().set(&Self::key_admin(), &admin);
+        env.storage().persistent().set(&Self::key_vk(), &vk_bytes);
+        env.storage().instance().set(&Self::key_vk_version(), &0u32);
+        for circuit_vk in circuit_vks.iter() {
+            load_vk_from_bytes(&circuit_vk).ok_or(Error::VkParseError)?;
+            let key = Self::key_circuit_vk(&Self::vk_id(env.clone(), circuit_vk.clone()));
+            let persistent = env.storage().persistent();
+            if persistent.has(&key) {
+                return Err(Error::DuplicateVk);
+            }
+            persistent.set(&key, &circuit_vk);
+            persistent.extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+        }
+        Self::extend_ttls(&env);
+        Ok(())
+    }
+
+    /// Identifier of a circuit's VK: the Keccak-256 of its bb bytes.
+    pub fn vk_id(env: Env, vk_bytes: Bytes) -> BytesN<32> {
+        env.crypto().keccak256(&vk_bytes).into()
+    }
+
+    /// `vk_id` of the stored VK, for clients caching it off-chain.
+    pub fn stored_vk_id(env: Env) -> Result<BytesN<32>, Error> {
+        let vk_bytes = Self::stored_vk_bytes(&env)?;
+        Ok(Self::vk_id(env, vk_bytes))
+    }
+
+    /// Whether a circuit VK was registered at deploy time under `vk_id`.
+    pub fn has_circuit_vk(env: Env, vk_id: BytesN<32>) -> bool {
+        env.storage()
+            .persistent()
+            .has(&Self::key_circuit_vk(&vk_id))
+    }
+
+    /// Replace the stored VK (admin only).
+    ///
+    /// `expected_version` must equal the current `vk_version`, so a stale or
+    /// reordered update is rejected instead of overwriting a newer VK.
+    /// Returns the new version.
+    pub fn set_vk(env: Env, vk_bytes: Bytes, expected_version: u32) -> Result<u32, Error> {
+        Self::require_admin(&env)?;
+        let current = Self::vk_version(env.clone());
+        if current != expected_version {
+            return Err(Error::VkVersionMismatch);
+        }
+        load_vk_from_bytes(&vk_bytes).ok_or(Error::VkParseError)?;
+        Self::install_vk(&env, &vk_bytes, current)
+    }
+
+    /// `set_vk`, but only once `migration_proof` verifies against the new VK,
+    /// so a VK no valid proof exists for cannot be installed. A rejected
+    /// migration proof fails with `MigrationProofInvalid` and leaves the
+    /// stored VK and its version untouched.
+    pub fn rotate_vk(
+        env: Env,
+        new_vk_bytes: Bytes,
+        migration_proof: Bytes,
+        migration_public_inputs: Bytes,
+        expected_version: u32,
+    ) -> Result<u32, Error> {
+        Self::require_admin(&env)?;
+        let current = Self::vk_version(env.clone());
+        if current != expected_version {
+            return Err(Error::VkVersionMismatch);
+        }
+        if migration_proof.len() as usize != PROOF_BYTES {
+            return Err(Error::ProofParseError);
+        }
+        let verifier =
+            UltraHonkVerifier::new(&env, &new_vk_bytes).map_err(|_| Error::VkParseError)?;
+        verifier
+            .verify(&migration_proof, &migration_public_inputs)
+            .map_err(|_| Error::MigrationProofInvalid)?;
+        Self::install_vk(&env, &new_vk_bytes, current)
+    }
+
+    /// Store an already validated raw VK as version `current + 1`.
+    fn install_vk(env: &Env, vk_bytes: &Bytes, current: u32) -> Result<u32, Error> {
+        let next = current.checked_add(1).ok_or(Error::VkVersionMismatch)?;
+        env.storage().persistent().remove(&Self::key_vk_packed());
+        env.storage().persistent().set(&Self::key_vk(), vk_bytes);
+        env.storage().instance().set(&Self::key_vk_version(), &next);
+        Self::extend_ttls(env);
+        Ok(next)
+    }
+
+    /// `set_vk` for a VK packed with `zero_rle_compress`, which is kept packed
+    /// and unpacked on each load. The identity commitments of simple circuits
+    /// are all zero bytes, so this can shrink the stored entry a lot; `vk_id`
+    /// still hashes the unpacked bytes.
+    pub fn set_vk_compressed(
+        env: Env,
+        compressed_vk: Bytes,
+        expected_version: u32,
+    ) -> Result<u32, Error> {
+        Self::require_admin(&env)?;
+        let current = Self::vk_version(env.clone());
+        if current != expected_version {
+            return Err(Error::VkVersionMismatch);
+        }
+        load_vk_from_bytes(&Self::unpack_vk(&env, &compressed_vk)?).ok_or(Error::VkParseError)?;
+        let next = current.checked_add(1).ok_or(Error::VkVersionMismatch)?;
+        env.storage().persistent().remove(&Self::key_vk());
+        env.storage()
+            .persistent()
+            .set(&Self::key_vk_packed(), &compressed_vk);
+        env.storage().instance().set(&Self::key_vk_version(), &next);
+        Self::extend_ttls(&env);
+        Ok(next)
+    }
+
+    /// Extend the TTL of the instance and the stored VK (admin only).
+    ///
+    /// Reads and updates already extend both; this is for contracts that may
+    /// sit idle longer than `TTL_EXTEND_TO` ledgers.
+    pub fn bump_ttl(env: Env) -> Result<(), Error> {
+        Self::require_admin(&env)?;
+        Self::extend_ttls(&env);
+        Ok(())
+    }
+
+    /// Number of successful `set_vk` calls since deploy.
+    pub fn vk_version(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&Self::key_vk_version())
+            .unwrap_or(0)
+    }
+
     /// Verify an UltraHonk proof using the stored VK.
+    ///
+    /// VK and proof are parsed straight from the host `Bytes` (one copy each
+    /// into a fixed-size buffer), with no intermediate `Vec<u8>`.
     pub fn verify_proof(env: Env, public_inputs: Bytes, proof_bytes: Bytes) -> Result<(), Error> {
         if proof_bytes.len() as usize != PROOF_BYTES {
             return Err(Error::ProofParseError);
         }
 
-        let vk_bytes: Bytes = env
-            .storage()
-            .instance()
-            .get(&Self::key_vk())
-            .ok_or(Error::VkNotSet)?;
-        // Deserialize verification key bytes
+        let verifier = Self::stored_verifier(&env)?;
+        verifier
+            .verify(&proof_bytes, &public_inputs)
+            .map_err(|e| error_from_verify(&e))?;
+        Ok(())
+    }
+
+    /// Verify like `verify_proof`, but first fail with `VkHashMismatch` if
+    /// the stored VK is no longer the one whose `vk_id` the caller expects,
+    /// e.g. because `set_vk` replaced it.
+    pub fn verify_proof_expecting(
+        env: Env,
+        expected_vk_id: BytesN<32>,
+        public_inputs: Bytes,
+        proof_bytes: Bytes,
+    ) -> Result<(), Error> {
+        if Self::stored_vk_id(env.clone())? != expected_vk_id {
+            return Err(Error::VkHashMismatch);
+        }
+        Self::verify_proof(env, public_inputs, proof_bytes)
+    }
+
+    /// Dry-run verification of `proof_bytes` against `vk_bytes`, reporting
+    /// which stage failed instead of reverting. Nothing is stored; use
+    /// `verify_proof` where the outcome must be enforced.
+    ///
+    /// Host errors still trap, e.g. a VK point that is not on the curve.
+    pub fn inspect_proof(
+        env: Env,
+        vk_bytes: Bytes,
+        public_inputs: Bytes,
+        proof_bytes: Bytes,
+    ) -> VerifyReport {
+        let mut report = VerifyReport {
+            vk_parsed: false,
+            inputs_valid: false,
+            sumcheck_passed: false,
+            pairing_passed: false,
+            error_code: 0,
+            detail: String::from_str(&env, ""),
+        };
+        let outcome = UltraHonkVerifier::new(&env, &vk_bytes).and_then(|verifier| {
+            report.vk_parsed = true;
+            if proof_bytes.len() as usize != PROOF_BYTES {
+                return Err(VerifyError::InvalidInput("proof bytes len"));
+            }
+            verifier.verify(&proof_bytes, &public_inputs)
+        });
+        match outcome {
+            Ok(()) => {
+                report.inputs_valid = true;
+                report.sumcheck_passed = true;
+                report.pairing_passed = true;
+            }
+            Err(e) => {
+                report.inputs_valid = !matches!(e, VerifyError::InvalidInput(_));
+                report.sumcheck_passed = matches!(e, VerifyError::ShplonkFailed(_));
+                report.error_code = e.code();
+                let msg = match e {
+                    VerifyError::InvalidInput(msg) | VerifyError::SumcheckFailed(msg) => msg,
+                    VerifyError::ShplonkFailed(err) => err.message(),
+                };
+                report.detail = String::from_str(&env, msg);
+            }
+        }
+        report
+    }
+
+    /// Verify like `verify_proof`, against the circuit VK registered under
+    /// `vk_id` instead of the stored VK. Fails with `VkNotSet` for an
+    /// unknown id.
+    pub fn verify_proof_for_circuit(
+        env: Env,
+        vk_id: BytesN<32>,
+        public_inputs: Bytes,
+        proof_bytes: Bytes,
+    ) -> Result<(), Error> {
+        if proof_bytes.len() as usize != PROOF_BYTES {
+            return Err(Error::ProofParseError);
+        }
+        let key = Self::key_circuit_vk(&vk_id);
+        let persistent = env.storage().persistent();
+        let vk_bytes: Bytes = persistent.get(&key).ok_or(Error::VkNotSet)?;
+        persistent.extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
         let verifier = UltraHonkVerifier::new(&env, &vk_bytes).map_err(|_| Error::VkParseError)?;
+        verifier
+            .verify(&proof_bytes, &public_inputs)
+            .map_err(|e| error_from_verify(&e))?;
+        Ok(())
+    }
 
-        // Verify
+    /// Verify like `verify_proof`, against the VK `registry` returns from
+    /// `get_vk(circuit_id) -> Option<Bytes>`. Fails with `VkNotSet` if it
+    /// has none or the call fails. The registry is trusted to return the
+    /// right VK; callers choose which registry to trust.
+    pub fn verify_proof_with_registry(
+        env: Env,
+        registry: Address,
+        circuit_id: BytesN<32>,
+        public_inputs: Bytes,
+        proof_bytes: Bytes,
+    ) -> Result<(), Error> {
+        if proof_bytes.len() as usize != PROOF_BYTES {
+            return Err(Error::ProofParseError);
+        }
+        let vk_bytes = env
+            .try_invoke_contract::<Option<Bytes>, InvokeError>(
+                &registry,
+                &Symbol::new(&env, "get_vk"),
+                vec![&env, circuit_id.into_val(&env)],
+            )
+            .ok()
+            .and_then(Result::ok)
+            .flatten()
+            .ok_or(Error::VkNotSet)?;
+        let verifier = UltraHonkVerifier::new(&env, &vk_bytes).map_err(|_| Error::VkParseError)?;
         verifier
             .verify(&proof_bytes, &public_inputs)
-            .map_err(|_| Error::VerificationFailed)?;
+            .map_err(|e| error_from_verify(&e))?;
+        Ok(())
+    }
+
+    /// Verify like `verify_proof`, taking public inputs and proof as one
+    /// zero-run-compressed envelope: `zero_rle_compress(pack_envelope(..))`
+    /// from `ultrahonk_soroban_verifier::utils`. The many zero limb bytes
+    /// make this markedly smaller than the raw ~14.6KB proof.
+    pub fn verify_proof_compressed(env: Env, compressed: Bytes) -> Result<(), Error> {
+        let verifier = Self::stored_verifier(&env)?;
+        let public_inputs_len = verifier
+            .get_vk()
+            .public_inputs_size
+            .saturating_sub(verifier.get_version().pairing_points_size() as u64)
+            as usize
+            * 32;
+        let mut packed = alloc::vec![0u8; compressed.len() as usize];
+        compressed.copy_into_slice(&mut packed);
+        let envelope = zero_rle_decompress(
+            &packed,
+            ENVELOPE_HEADER_BYTES + public_inputs_len + PROOF_BYTES,
+        )
+        .map_err(|_| Error::ProofParseError)?;
+        let parsed = ProofEnvelope::parse(&envelope).map_err(|_| Error::ProofParseError)?;
+        verifier
+            .verify(
+                &Bytes::from_slice(&env, parsed.proof),
+                &Bytes::from_slice(&env, parsed.public_inputs),
+            )
+            .map_err(|e| error_from_verify(&e))?;
+        Ok(())
+    }
+
+    /// A verifier over the stored VK, extending its TTL.
+    fn stored_verifier(env: &Env) -> Result<UltraHonkVerifier, Error> {
+        let vk_bytes = Self::stored_vk_bytes(env)?;
+        Self::extend_ttls(env);
+        UltraHonkVerifier::new(env, &vk_bytes).map_err(|_| Error::VkParseError)
+    }
+
+    /// Verify a proof and mark `consumable_key` (e.g. a nullifier) as used,
+    /// in one call. Fails with `KeyAlreadyConsumed` if the key was used
+    /// before; any failure reverts the whole call, so the key is only
+    /// consumed by a proof that verified.
+    ///
+    /// The key must be the public input at `consumable_key_index()`, else
+    /// `KeyMismatch`: a valid proof consumes only its own key, so it can
+    /// neither be replayed under a fresh key nor burn someone else's. Keys
+    /// are kept in persistent storage so they are never dropped, only
+    /// archived.
+    pub fn verify_and_consume(
+        env: Env,
+        public_inputs: Bytes,
+        proof_bytes: Bytes,
+        consumable_key: BytesN<32>,
+    ) -> Result<(), Error> {
+        let key = Self::key_consumed(&consumable_key);
+        if env.storage().persistent().has(&key) {
+            return Err(Error::KeyAlreadyConsumed);
+        }
+        let start = Self::consumable_key_index(env.clone()) * 32;
+        if public_inputs.len() < start + 32 {
+            return Err(Error::InvalidInput);
+        }
+        if public_inputs.slice(start..start + 32) != Bytes::from(&consumable_key) {
+            return Err(Error::KeyMismatch);
+        }
+        Self::verify_proof(env.clone(), public_inputs, proof_bytes)?;
+        env.storage().persistent().set(&key, &true);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+        Ok(())
+    }
+
+    /// Set which public input `verify_and_consume` takes as the key (admin
+    /// only).
+    pub fn set_consumable_key_index(env: Env, index: u32) -> Result<(), Error> {
+        Self::require_admin(&env)?;
+        env.storage()
+            .instance()
+            .set(&Self::key_consumable_index(), &index);
+        Self::extend_ttls(&env);
+        Ok(())
+    }
+
+    /// Index of the public input `verify_and_consume` takes as the key; 0
+    /// until the admin sets another.
+    pub fn consumable_key_index(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&Self::key_consumable_index())
+            .unwrap_or(0)
+    }
+
+    /// Set the address allowed to settle deferred pairings (admin only).
+    pub fn set_pairing_oracle(env: Env, oracle: Address) -> Result<(), Error> {
+        Self::require_admin(&env)?;
+        env.storage().instance().set(&Self::key_oracle(), &oracle);
+        Self::extend_ttls(&env);
         Ok(())
     }
+
+    /// Verify a proof against `vk_bytes` up to, but not including, the
+    /// pairing, for hosts without the BN254 pairing function.
+    ///
+    /// Stores the two G1 points `(P0, P1)` (see
+    /// `UltraHonkVerifier::verify_pairing_terms` for the G2 side) under a
+    /// fresh pending id, with the `PairingBinding` of the VK and public
+    /// inputs they came from, and returns the id. The proof is only accepted
+    /// once the pairing oracle reports the pairing valid via
+    /// `finalize_pairing`; until then `pairing_result` fails with
+    /// `PairingPending`.
+    ///
+    /// The VK is the caller's, so consumers must check the returned binding.
+    /// `submitter` must authorize the call: each call adds a persistent
+    /// entry, and the submitter's transaction pays its rent.
+    pub fn verify_proof_deferred_pairing(
+        env: Env,
+        submitter: Address,
+        vk_bytes: Bytes,
+        public_inputs: Bytes,
+        proof_bytes: Bytes,
+    ) -> Result<u32, Error> {
+        submitter.require_auth();
+        if proof_bytes.len() as usize != PROOF_BYTES {
+            return Err(Error::ProofParseError);
+        }
+        let verifier = UltraHonkVerifier::new(&env, &vk_bytes).map_err(|_| Error::VkParseError)?;
+        let (p0, p1) = verifier
+            .verify_up_to_pairing(&proof_bytes, &public_inputs)
+            .map_err(|e| error_from_verify(&e))?;
+
+        let id: u32 = env
+            .storage()
+            .instance()
+            .get(&Self::key_next_pending())
+            .unwrap_or(0);
+        let next = id.checked_add(1).ok_or(Error::InvalidInput)?;
+        env.storage()
+            .instance()
+            .set(&Self::key_next_pending(), &next);
+        let key = Self::key_pending(id);
+        let pending = PendingPairing {
+            binding: PairingBinding {
+                vk_id: Self::vk_id(env.clone(), vk_bytes),
+                public_inputs_hash: env.crypto().keccak256(&public_inputs).into(),
+                submitter,
+            },
+            p0: p0.to_bytes(),
+            p1: p1.to_bytes(),
+        };
+        env.storage().persistent().set(&key, &pending);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+        Self::extend_ttls(&env);
+        Ok(id)
+    }
+
+    /// The points awaiting the oracle under `pending_id`, with their binding.
+    pub fn pending_pairing(env: Env, pending_id: u32) -> Option<PendingPairing> {
+        env.storage()
+            .persistent()
+            .get(&Self::key_pending(pending_id))
+    }
+
+    /// Settle a deferred pairing (pairing oracle only).
+    ///
+    /// The pending points are dropped and the verdict recorded, with the
+    /// binding, for `pairing_result`; an id can only be settled once.
+    pub fn finalize_pairing(
+        env: Env,
+        pending_id: u32,
+        pairing_was_valid: bool,
+    ) -> Result<(), Error> {
+        let oracle: Address = env
+            .storage()
+            .instance()
+            .get(&Self::key_oracle())
+            .ok_or(Error::PairingOracleNotSet)?;
+        oracle.require_auth();
+
+        let persistent = env.storage().persistent();
+        let key = Self::key_pending(pending_id);
+        let pending: PendingPairing = persistent.get(&key).ok_or(Error::PairingNotFound)?;
+        persistent.remove(&key);
+        let paired = Self::key_paired(pending_id);
+        persistent.set(&paired, &(pending.binding, pairing_was_valid));
+        persistent.extend_ttl(&paired, TTL_THRESHOLD, TTL_EXTEND_TO);
+        Ok(())
+    }
+
+    /// Outcome of a deferred pairing: the VK and public inputs it was
+    /// checked against once the oracle reported it valid,
+    /// `VerificationFailed` if it reported it invalid, `PairingPending`
+    /// while unsettled.
+    pub fn pairing_result(env: Env, pending_id: u32) -> Result<PairingBinding, Error> {
+        let persistent = env.storage().persistent();
+        match persistent.get::<_, (PairingBinding, bool)>(&Self::key_paired(pending_id)) {
+            Some((binding, true)) => Ok(binding),
+            Some((_, false)) => Err(Error::VerificationFailed),
+            None if persistent.has(&Self::key_pending(pending_id)) => Err(Error::PairingPending),
+            None => Err(Error::PairingNotFound),
+        }
+    }
+
+    /// Id of a proof against the stored VK:
+    /// `keccak256(stored_vk_id() || public_inputs || proof)`.
+    ///
+    /// Binding the VK means the same proof bytes get a new id after
+    /// `set_vk`.
+    pub fn proof_id(
+        env: Env,
+        public_inputs: Bytes,
+        proof_bytes: Bytes,
+    ) -> Result<BytesN<32>, Error> {
+        let mut preimage = Bytes::from_array(&env, &Self::stored_vk_id(env.clone())?.to_array());
+        preimage.append(&public_inputs);
+        preimage.append(&proof_bytes);
+        Ok(env.crypto().keccak256(&preimage).into())
+    }
+
+    /// Verify like `verify_proof`, then record and return the `proof_id`,
+    /// so other contracts can check `is_verified` instead of re-verifying.
+    pub fn verify_and_record(
+        env: Env,
+        public_inputs: Bytes,
+        proof_bytes: Bytes,
+    ) -> Result<BytesN<32>, Error> {
+        Self::verify_proof(env.clone(), public_inputs.clone(), proof_bytes.clone())?;
+        let proof_id = Self::proof_id(env.clone(), public_inputs, proof_bytes)?;
+        let key = Self::key_verified(&proof_id);
+        env.storage().persistent().set(&key, &true);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+        Ok(proof_id)
+    }
+
+    /// Verify like `verify_proof` and return a keccak digest of the accepted
+    /// proof's Fiat–Shamir challenges (`transcript::challenge_digest`), for an
+    /// audit trail that can later re-derive and compare them.
+    pub fn verify_and_commit_transcript(
+        env: Env,
+        public_inputs: Bytes,
+        proof_bytes: Bytes,
+    ) -> Result<BytesN<32>, Error> {
+        if proof_bytes.len() as usize != PROOF_BYTES {
+            return Err(Error::ProofParseError);
+        }
+        let digest = Self::stored_verifier(&env)?
+            .verify_and_commit_transcript(&proof_bytes, &public_inputs)
+            .map_err(|e| error_from_verify(&e))?;
+        Ok(BytesN::from_array(&env, &digest))
+    }
+
+    /// Whether `verify_and_record` has recorded `proof_id`.
+    pub fn is_verified(env: Env, proof_id: BytesN<32>) -> bool {
+        env.storage()
+            .persistent()
+            .has(&Self::key_verified(&proof_id))
+    }
+
+    /// Whether `verify_and_consume` has already used `key`.
+    pub fn is_consumed(env: Env, key: BytesN<32>) -> bool {
+        env.storage().persistent().has(&Self::key_consumed(&key))
+    }
+}
+
+/// Known-good simple_circuit proof, VK and public inputs for `self_test`.
+#[cfg(feature = "self-test")]
+mod self_test_fixture {
+    pub const VK: &[u8] = include_bytes!("../tests/simple_circuit/target/vk");
+    pub const PROOF: &[u8] = include_bytes!("../tests/simple_circuit/target/proof");
+    pub const PUBLIC_INPUTS: &[u8] = include_bytes!("../tests/simple_circuit/target/public_inputs");
+}
+
+#[cfg(feature = "self-test")]
+#[contractimpl]
+impl UltraHonkVerifierContract {
+    /// Verify the embedded simple_circuit proof end to end, independent of
+    /// the stored VK. A no-argument post-deploy check that the Wasm and the
+    /// BN254/Keccak host functions behave on this network.
+    pub fn self_test(env: Env) -> bool {
+        let vk = Bytes::from_slice(&env, self_test_fixture::VK);
+        let proof = Bytes::from_slice(&env, self_test_fixture::PROOF);
+        let public_inputs = Bytes::from_slice(&env, self_test_fixture::PUBLIC_INPUTS);
+        UltraHonkVerifier::new(&env, &vk)
+            .and_then(|verifier| verifier.verify(&proof, &public_inputs))
+            .is_ok()
+    }
 }
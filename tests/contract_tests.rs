@@ -0,0 +1,534 @@
+//! Natively registered contract tests; these don't need the optimized Wasm.
+
+#[path = "../ultrahonk-soroban-verifier/tests/common/mod.rs"]
+mod common;
+
+use common::zero_vk_bytes;
+use rs_soroban_ultrahonk::{
+    Error, PairingBinding, PendingPairing, UltraHonkVerifierContract,
+    UltraHonkVerifierContractClient, TTL_EXTEND_TO,
+};
+use soroban_sdk::{
+    contract, contractimpl, symbol_short,
+    testutils::{storage::Persistent as _, Address as _, Ledger as _},
+    Address, Bytes, BytesN, Env, String, Vec,
+};
+use ultrahonk_soroban_verifier::{
+    utils::{zero_rle_compress, VK_BYTES},
+    PROOF_BYTES,
+};
+
+/// A VK that parses (points are only checked by the host during the MSM).
+fn dummy_vk(env: &Env, tag: u8) -> Bytes {
+    let mut raw = zero_vk_bytes(4, 18);
+    raw[VK_BYTES - 1] = tag;
+    Bytes::from_slice(env, &raw)
+}
+
+/// A proof that parses but fails sum-check: every word is 1, so round 0's
+/// `u[0] + u[1]` is 2 rather than 0.
+fn failing_proof(env: &Env) -> Bytes {
+    let mut raw = [0u8; PROOF_BYTES];
+    raw.iter_mut().skip(31).step_by(32).for_each(|b| *b = 1);
+    Bytes::from_slice(env, &raw)
+}
+
+fn register(env: &Env, vk: &Bytes) -> UltraHonkVerifierContractClient<'static> {
+    let admin = Address::generate(env);
+    let id = env.register(
+        UltraHonkVerifierContract,
+        (admin, vk.clone(), Vec::<Bytes>::new(env)),
+    );
+    UltraHonkVerifierContractClient::new(env, &id)
+}
+
+#[test]
+fn set_vk_bumps_version_and_rejects_stale_updates() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = register(&env, &dummy_vk(&env, 0));
+    assert_eq!(client.vk_version(), 0);
+
+    assert_eq!(client.set_vk(&dummy_vk(&env, 1), &0), 1);
+    assert_eq!(client.vk_version(), 1);
+
+    // A second update still built against version 0 is stale.
+    let err = client.try_set_vk(&dummy_vk(&env, 2), &0);
+    assert_eq!(err, Err(Ok(Error::VkVersionMismatch)));
+    assert_eq!(client.vk_version(), 1);
+
+    assert_eq!(client.set_vk(&dummy_vk(&env, 2), &1), 2);
+}
+
+#[test]
+fn rotate_vk_with_an_invalid_migration_proof_keeps_the_old_vk() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.cost_estimate().budget().reset_unlimited();
+    let client = register(&env, &dummy_vk(&env, 0));
+    let old_id = client.stored_vk_id();
+    let new_vk = dummy_vk(&env, 1);
+    let proof = failing_proof(&env);
+    let public_inputs = Bytes::from_slice(&env, &[0u8; 64]);
+
+    let err = client.try_rotate_vk(&new_vk, &proof, &public_inputs, &0);
+    assert_eq!(err, Err(Ok(Error::MigrationProofInvalid)));
+    let short = Bytes::from_slice(&env, &[1u8; 32]);
+    let err = client.try_rotate_vk(&new_vk, &short, &public_inputs, &0);
+    assert_eq!(err, Err(Ok(Error::ProofParseError)));
+    let err = client.try_rotate_vk(&new_vk, &proof, &public_inputs, &1);
+    assert_eq!(err, Err(Ok(Error::VkVersionMismatch)));
+
+    assert_eq!(client.stored_vk_id(), old_id);
+    assert_eq!(client.vk_version(), 0);
+}
+
+#[test]
+fn set_vk_rejects_unparseable_vk() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = register(&env, &dummy_vk(&env, 0));
+    let err = client.try_set_vk(&Bytes::from_slice(&env, &[0u8; 10]), &0);
+    assert_eq!(err, Err(Ok(Error::VkParseError)));
+    assert_eq!(client.vk_version(), 0);
+}
+
+#[test]
+#[should_panic]
+fn set_vk_requires_admin_auth() {
+    let env = Env::default();
+    let client = register(&env, &dummy_vk(&env, 0));
+    client.set_vk(&dummy_vk(&env, 1), &0);
+}
+
+fn vk_ttl(env: &Env, client: &UltraHonkVerifierContractClient) -> u32 {
+    env.as_contract(&client.address, || {
+        env.storage().persistent().get_ttl(&symbol_short!("vk"))
+    })
+}
+
+#[test]
+fn bump_ttl_keeps_the_vk_alive_past_its_original_expiry() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = register(&env, &dummy_vk(&env, 0));
+    assert_eq!(vk_ttl(&env, &client), TTL_EXTEND_TO);
+
+    // Idle until the entry is about to be archived, then bump it.
+    env.ledger()
+        .with_mut(|l| l.sequence_number += TTL_EXTEND_TO - 10);
+    assert_eq!(vk_ttl(&env, &client), 10);
+    client.bump_ttl();
+    assert_eq!(vk_ttl(&env, &client), TTL_EXTEND_TO);
+
+    // Past the original expiry the VK is still readable and replaceable.
+    env.ledger().with_mut(|l| l.sequence_number += 20);
+    assert_eq!(client.set_vk(&dummy_vk(&env, 1), &0), 1);
+}
+
+#[test]
+#[should_panic]
+fn bump_ttl_requires_admin_auth() {
+    let env = Env::default();
+    let client = register(&env, &dummy_vk(&env, 0));
+    client.bump_ttl();
+}
+
+#[test]
+fn failed_verification_does_not_consume_the_key() {
+    let env = Env::default();
+    env.cost_estimate().budget().reset_unlimited();
+    let client = register(&env, &dummy_vk(&env, 0));
+    let key = BytesN::from_array(&env, &[0u8; 32]);
+    let proof = failing_proof(&env);
+    let public_inputs = Bytes::from_slice(&env, &[0u8; 64]);
+
+    let err = client.try_verify_and_consume(&public_inputs, &proof, &key);
+    assert_eq!(err, Err(Ok(Error::VerificationFailed)));
+    assert!(!client.is_consumed(&key));
+}
+
+#[test]
+fn the_consumed_key_must_be_the_configured_public_input() {
+    let env = Env::default();
+    env.cost_estimate().budget().reset_unlimited();
+    env.mock_all_auths();
+    let client = register(&env, &dummy_vk(&env, 0));
+    let proof = failing_proof(&env);
+    let mut raw = [0u8; 64];
+    raw[32..].fill(0x42);
+    let public_inputs = Bytes::from_slice(&env, &raw);
+    let key = BytesN::from_array(&env, &[0x42; 32]);
+
+    assert_eq!(client.consumable_key_index(), 0);
+    let err = client.try_verify_and_consume(&public_inputs, &proof, &key);
+    assert_eq!(err, Err(Ok(Error::KeyMismatch)));
+
+    // Bound to input 1 the key matches, so the proof itself is checked.
+    client.set_consumable_key_index(&1);
+    assert_eq!(client.consumable_key_index(), 1);
+    let err = client.try_verify_and_consume(&public_inputs, &proof, &key);
+    assert_eq!(err, Err(Ok(Error::VerificationFailed)));
+
+    // An index past the public inputs binds nothing.
+    client.set_consumable_key_index(&2);
+    let err = client.try_verify_and_consume(&public_inputs, &proof, &key);
+    assert_eq!(err, Err(Ok(Error::InvalidInput)));
+    assert!(!client.is_consumed(&key));
+}
+
+#[test]
+fn malformed_public_inputs_map_to_invalid_input() {
+    let env = Env::default();
+    env.cost_estimate().budget().reset_unlimited();
+    let client = register(&env, &dummy_vk(&env, 0));
+    let proof = failing_proof(&env);
+
+    // The VK expects two public inputs.
+    let public_inputs = Bytes::from_slice(&env, &[0u8; 32]);
+    let err = client.try_verify_proof(&public_inputs, &proof);
+    assert_eq!(err, Err(Ok(Error::InvalidInput)));
+}
+
+#[test]
+fn a_failed_deferred_verification_leaves_nothing_pending() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.cost_estimate().budget().reset_unlimited();
+    let vk = dummy_vk(&env, 0);
+    let client = register(&env, &vk);
+    let proof = failing_proof(&env);
+    let public_inputs = Bytes::from_slice(&env, &[0u8; 64]);
+
+    let submitter = Address::generate(&env);
+    let err = client.try_verify_proof_deferred_pairing(&submitter, &vk, &public_inputs, &proof);
+    assert_eq!(err, Err(Ok(Error::VerificationFailed)));
+    assert_eq!(client.pending_pairing(&0), None);
+    assert_eq!(
+        client.try_pairing_result(&0),
+        Err(Ok(Error::PairingNotFound))
+    );
+}
+
+#[test]
+fn deferred_pairing_is_settled_once_by_the_oracle() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = register(&env, &dummy_vk(&env, 0));
+    let err = client.try_finalize_pairing(&0, &true);
+    assert_eq!(err, Err(Ok(Error::PairingOracleNotSet)));
+    client.set_pairing_oracle(&Address::generate(&env));
+
+    // Stand in for a proof that got through the MSM.
+    let binding = PairingBinding {
+        vk_id: BytesN::from_array(&env, &[3u8; 32]),
+        public_inputs_hash: BytesN::from_array(&env, &[4u8; 32]),
+        submitter: Address::generate(&env),
+    };
+    let pending = PendingPairing {
+        binding: binding.clone(),
+        p0: BytesN::from_array(&env, &[1u8; 64]),
+        p1: BytesN::from_array(&env, &[2u8; 64]),
+    };
+    env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .set(&(symbol_short!("pend"), 0u32), &pending);
+    });
+    assert_eq!(client.pending_pairing(&0), Some(pending));
+    assert_eq!(
+        client.try_pairing_result(&0),
+        Err(Ok(Error::PairingPending))
+    );
+
+    client.finalize_pairing(&0, &true);
+    assert_eq!(client.pending_pairing(&0), None);
+    assert_eq!(client.try_pairing_result(&0), Ok(Ok(binding)));
+    let err = client.try_finalize_pairing(&0, &false);
+    assert_eq!(err, Err(Ok(Error::PairingNotFound)));
+}
+
+#[test]
+#[should_panic]
+fn deferred_pairing_requires_submitter_auth() {
+    let env = Env::default();
+    let vk = dummy_vk(&env, 0);
+    let client = register(&env, &vk);
+    let proof = failing_proof(&env);
+    let public_inputs = Bytes::from_slice(&env, &[0u8; 64]);
+    client.verify_proof_deferred_pairing(&Address::generate(&env), &vk, &public_inputs, &proof);
+}
+
+#[test]
+#[should_panic]
+fn finalize_pairing_requires_oracle_auth() {
+    let env = Env::default();
+    let client = register(&env, &dummy_vk(&env, 0));
+    env.mock_all_auths();
+    client.set_pairing_oracle(&Address::generate(&env));
+    env.set_auths(&[]);
+    client.finalize_pairing(&0, &true);
+}
+
+#[cfg(feature = "self-test")]
+#[test]
+fn self_test_verifies_the_embedded_proof() {
+    let env = Env::default();
+    env.cost_estimate().budget().reset_unlimited();
+    let client = register(&env, &dummy_vk(&env, 0));
+    assert!(client.self_test());
+}
+
+#[test]
+fn malformed_compressed_envelopes_are_parse_errors() {
+    let env = Env::default();
+    env.cost_estimate().budget().reset_unlimited();
+    let client = register(&env, &dummy_vk(&env, 0));
+
+    // A dangling zero-run marker.
+    let truncated = Bytes::from_slice(&env, &[7, 0]);
+    let err = client.try_verify_proof_compressed(&truncated);
+    assert_eq!(err, Err(Ok(Error::ProofParseError)));
+
+    // Well-formed runs, but far more bytes than the VK allows.
+    let huge = Bytes::from_slice(&env, &[0, 255].repeat(200));
+    let err = client.try_verify_proof_compressed(&huge);
+    assert_eq!(err, Err(Ok(Error::ProofParseError)));
+}
+
+#[test]
+fn circuit_vks_registered_at_deploy_are_verified_against() {
+    let env = Env::default();
+    env.cost_estimate().budget().reset_unlimited();
+    let (a, b) = (dummy_vk(&env, 1), dummy_vk(&env, 2));
+    let admin = Address::generate(&env);
+    let id = env.register(
+        UltraHonkVerifierContract,
+        (
+            admin,
+            dummy_vk(&env, 0),
+            Vec::from_array(&env, [a.clone(), b.clone()]),
+        ),
+    );
+    let client = UltraHonkVerifierContractClient::new(&env, &id);
+    let proof = failing_proof(&env);
+    let public_inputs = Bytes::from_slice(&env, &[0u8; 64]);
+
+    for vk in [&a, &b] {
+        let vk_id = client.vk_id(vk);
+        assert!(client.has_circuit_vk(&vk_id));
+        // The proof reaches the verifier, which rejects it.
+        let err = client.try_verify_proof_for_circuit(&vk_id, &public_inputs, &proof);
+        assert_eq!(err, Err(Ok(Error::VerificationFailed)));
+    }
+    let unknown = client.vk_id(&dummy_vk(&env, 3));
+    assert!(!client.has_circuit_vk(&unknown));
+    let err = client.try_verify_proof_for_circuit(&unknown, &public_inputs, &proof);
+    assert_eq!(err, Err(Ok(Error::VkNotSet)));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #12)")]
+fn duplicate_circuit_vks_are_rejected_at_deploy() {
+    let env = Env::default();
+    let vk = dummy_vk(&env, 1);
+    env.register(
+        UltraHonkVerifierContract,
+        (
+            Address::generate(&env),
+            dummy_vk(&env, 0),
+            Vec::from_array(&env, [vk.clone(), vk]),
+        ),
+    );
+}
+
+#[test]
+fn verify_proof_expecting_detects_a_replaced_vk() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.cost_estimate().budget().reset_unlimited();
+    let client = register(&env, &dummy_vk(&env, 0));
+    let cached = client.stored_vk_id();
+    assert_eq!(cached, client.vk_id(&dummy_vk(&env, 0)));
+    let proof = failing_proof(&env);
+    let public_inputs = Bytes::from_slice(&env, &[0u8; 64]);
+
+    // Still current: the proof is checked (and rejected) by the verifier.
+    let err = client.try_verify_proof_expecting(&cached, &public_inputs, &proof);
+    assert_eq!(err, Err(Ok(Error::VerificationFailed)));
+
+    client.set_vk(&dummy_vk(&env, 1), &0);
+    let err = client.try_verify_proof_expecting(&cached, &public_inputs, &proof);
+    assert_eq!(err, Err(Ok(Error::VkHashMismatch)));
+}
+
+#[test]
+fn unaligned_public_inputs_are_not_proof_parse_errors() {
+    let env = Env::default();
+    env.cost_estimate().budget().reset_unlimited();
+    let client = register(&env, &dummy_vk(&env, 0));
+    let proof = failing_proof(&env);
+    let public_inputs = Bytes::from_slice(&env, &[0u8; 33]);
+    let err = client.try_verify_proof(&public_inputs, &proof);
+    assert_eq!(err, Err(Ok(Error::InvalidInput)));
+}
+
+#[test]
+fn inspect_proof_reports_the_failed_stage_without_reverting() {
+    let env = Env::default();
+    env.cost_estimate().budget().reset_unlimited();
+    let vk = dummy_vk(&env, 0);
+    let client = register(&env, &vk);
+    let proof = failing_proof(&env);
+
+    let report = client.inspect_proof(&vk, &Bytes::from_slice(&env, &[0u8; 64]), &proof);
+    assert!(report.vk_parsed && report.inputs_valid);
+    assert!(!report.sumcheck_passed && !report.pairing_passed);
+    assert_eq!(report.error_code, 2);
+
+    let report = client.inspect_proof(&vk, &Bytes::from_slice(&env, &[0u8; 33]), &proof);
+    assert!(report.vk_parsed && !report.inputs_valid);
+    assert_eq!(report.error_code, 1);
+    assert_eq!(
+        report.detail,
+        String::from_str(&env, "public inputs must be 32-byte aligned")
+    );
+
+    let report = client.inspect_proof(&Bytes::new(&env), &Bytes::new(&env), &proof);
+    assert!(!report.vk_parsed);
+    assert_eq!(report.detail, String::from_str(&env, "vk parse error"));
+}
+
+/// Registry holding one VK under `[7; 32]`.
+#[contract]
+struct MockVkRegistry;
+
+#[contractimpl]
+impl MockVkRegistry {
+    pub fn __constructor(env: Env, vk: Bytes) {
+        env.storage().instance().set(&symbol_short!("vk"), &vk);
+    }
+
+    pub fn get_vk(env: Env, circuit_id: BytesN<32>) -> Option<Bytes> {
+        if circuit_id.to_array() != [7; 32] {
+            return None;
+        }
+        env.storage().instance().get(&symbol_short!("vk"))
+    }
+}
+
+#[test]
+fn verify_proof_with_registry_fetches_the_vk() {
+    let env = Env::default();
+    env.cost_estimate().budget().reset_unlimited();
+    let client = register(&env, &dummy_vk(&env, 0));
+    let registry = env.register(MockVkRegistry, (dummy_vk(&env, 1),));
+    let proof = failing_proof(&env);
+    let public_inputs = Bytes::from_slice(&env, &[0u8; 64]);
+
+    let unknown = BytesN::from_array(&env, &[8; 32]);
+    let err = client.try_verify_proof_with_registry(&registry, &unknown, &public_inputs, &proof);
+    assert_eq!(err, Err(Ok(Error::VkNotSet)));
+
+    // Not a registry at all.
+    let known = BytesN::from_array(&env, &[7; 32]);
+    let err =
+        client.try_verify_proof_with_registry(&client.address, &known, &public_inputs, &proof);
+    assert_eq!(err, Err(Ok(Error::VkNotSet)));
+
+    // The VK is found and the proof reaches the verifier, which rejects it.
+    let err = client.try_verify_proof_with_registry(&registry, &known, &public_inputs, &proof);
+    assert_eq!(err, Err(Ok(Error::VerificationFailed)));
+}
+
+#[test]
+fn proof_id_binds_the_stored_vk_and_inputs() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = register(&env, &dummy_vk(&env, 0));
+    let proof = failing_proof(&env);
+    let public_inputs = Bytes::from_slice(&env, &[0u8; 64]);
+
+    let id = client.proof_id(&public_inputs, &proof);
+    assert_eq!(client.proof_id(&public_inputs, &proof), id);
+    let mut preimage = Bytes::from_array(&env, &client.stored_vk_id().to_array());
+    preimage.append(&public_inputs);
+    preimage.append(&proof);
+    assert_eq!(id, BytesN::from(env.crypto().keccak256(&preimage)));
+
+    let other_inputs = Bytes::from_slice(&env, &[1u8; 64]);
+    assert_ne!(client.proof_id(&other_inputs, &proof), id);
+    client.set_vk(&dummy_vk(&env, 1), &0);
+    assert_ne!(client.proof_id(&public_inputs, &proof), id);
+}
+
+#[test]
+fn rejected_proofs_are_not_recorded() {
+    let env = Env::default();
+    env.cost_estimate().budget().reset_unlimited();
+    let client = register(&env, &dummy_vk(&env, 0));
+    let proof = failing_proof(&env);
+    let public_inputs = Bytes::from_slice(&env, &[0u8; 64]);
+
+    let err = client.try_verify_and_record(&public_inputs, &proof);
+    assert_eq!(err, Err(Ok(Error::VerificationFailed)));
+    assert!(!client.is_verified(&client.proof_id(&public_inputs, &proof)));
+}
+
+#[test]
+fn compressed_vk_is_stored_packed_and_behaves_like_the_raw_one() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.cost_estimate().budget().reset_unlimited();
+    let client = register(&env, &dummy_vk(&env, 0));
+    let vk = dummy_vk(&env, 1);
+    let mut raw = [0u8; VK_BYTES];
+    vk.copy_into_slice(&mut raw);
+    let packed = Bytes::from_slice(&env, &zero_rle_compress(&raw));
+    assert!(packed.len() < 32);
+
+    let err = client.try_set_vk_compressed(&Bytes::from_slice(&env, &[0u8, 10]), &0);
+    assert_eq!(err, Err(Ok(Error::VkParseError)));
+    assert_eq!(client.set_vk_compressed(&packed, &0), 1);
+    assert_eq!(client.stored_vk_id(), client.vk_id(&vk));
+    env.as_contract(&client.address, || {
+        let persistent = env.storage().persistent();
+        assert!(!persistent.has(&symbol_short!("vk")));
+        assert_eq!(
+            persistent.get(&symbol_short!("vk_rle")),
+            Some(packed.clone())
+        );
+    });
+
+    let proof = failing_proof(&env);
+    let public_inputs = Bytes::from_slice(&env, &[0u8; 64]);
+    let packed_result = client.try_verify_proof(&public_inputs, &proof);
+    assert_eq!(client.set_vk(&vk, &1), 2);
+    assert_eq!(
+        client.try_verify_proof(&public_inputs, &proof),
+        packed_result
+    );
+    env.as_contract(&client.address, || {
+        assert!(!env.storage().persistent().has(&symbol_short!("vk_rle")));
+    });
+}
+
+#[test]
+fn malformed_openings_are_told_apart_from_failed_proofs() {
+    let env = Env::default();
+    env.cost_estimate().budget().reset_unlimited();
+    let client = register(&env, &dummy_vk(&env, 0));
+    let public_inputs = Bytes::from_slice(&env, &[0u8; 64]);
+
+    // All-zero univariates pass sum-check; the identity shplonk_q then
+    // makes the opening reject the proof as malformed.
+    let zero = Bytes::from_slice(&env, &[0u8; PROOF_BYTES]);
+    assert_eq!(
+        client.try_verify_proof(&public_inputs, &zero),
+        Err(Ok(Error::MalformedProof))
+    );
+    assert_eq!(
+        client.try_verify_proof(&public_inputs, &failing_proof(&env)),
+        Err(Ok(Error::VerificationFailed))
+    );
+}
@@ -1,5 +1,10 @@
-use soroban_sdk::{Bytes, Env};
-use ultrahonk_soroban_verifier::PROOF_BYTES;
+use soroban_sdk::{
+    contract, contractimpl, symbol_short, testutils::Address as _, Address, Bytes, BytesN, Env, Vec,
+};
+use ultrahonk_soroban_verifier::{
+    utils::{pack_envelope, zero_rle_compress},
+    PROOF_BYTES,
+};
 
 const CONTRACT_WASM: &[u8] =
     include_bytes!("../target/wasm32v1-none/release/rs_soroban_ultrahonk.wasm");
@@ -9,7 +14,11 @@ mod ultrahonk_contract {
 }
 
 fn register_client<'a>(env: &'a Env, vk_bytes: &Bytes) -> ultrahonk_contract::Client<'a> {
-    let contract_id = env.register(CONTRACT_WASM, (vk_bytes.clone(),));
+    let admin = Address::generate(env);
+    let contract_id = env.register(
+        CONTRACT_WASM,
+        (admin, vk_bytes.clone(), Vec::<Bytes>::new(env)),
+    );
     ultrahonk_contract::Client::new(env, &contract_id)
 }
 
@@ -51,6 +60,243 @@ fn verify_fib_chain_proof_succeeds() {
     client.verify_proof(&public_inputs, &proof_bytes);
 }
 
+#[test]
+fn verify_compressed_simple_circuit_proof_succeeds() {
+    let vk_bytes_raw: &[u8] = include_bytes!("simple_circuit/target/vk");
+    let proof_bin: &[u8] = include_bytes!("simple_circuit/target/proof");
+    let pub_inputs_bin: &[u8] = include_bytes!("simple_circuit/target/public_inputs");
+
+    let env = Env::default();
+    env.cost_estimate().budget().reset_unlimited();
+    let envelope = pack_envelope(pub_inputs_bin, proof_bin).unwrap();
+    let compressed = zero_rle_compress(&envelope);
+    assert!(compressed.len() < envelope.len() / 2);
+
+    let client = register_client(&env, &Bytes::from_slice(&env, vk_bytes_raw));
+    client.verify_proof_compressed(&Bytes::from_slice(&env, &compressed));
+}
+
+#[test]
+fn verify_against_circuit_vks_registered_at_deploy() {
+    let env = Env::default();
+    env.cost_estimate().budget().reset_unlimited();
+    let circuits: [(&[u8], &[u8], &[u8]); 2] = [
+        (
+            include_bytes!("simple_circuit/target/vk"),
+            include_bytes!("simple_circuit/target/proof"),
+            include_bytes!("simple_circuit/target/public_inputs"),
+        ),
+        (
+            include_bytes!("fib_chain/target/vk"),
+            include_bytes!("fib_chain/target/proof"),
+            include_bytes!("fib_chain/target/public_inputs"),
+        ),
+    ];
+    let mut vks = Vec::new(&env);
+    for (vk, _, _) in circuits {
+        vks.push_back(Bytes::from_slice(&env, vk));
+    }
+    let admin = Address::generate(&env);
+    let contract_id = env.register(
+        CONTRACT_WASM,
+        (admin, Bytes::from_slice(&env, circuits[0].0), vks),
+    );
+    let client = ultrahonk_contract::Client::new(&env, &contract_id);
+
+    for (vk, proof, public_inputs) in circuits {
+        let vk_id = client.vk_id(&Bytes::from_slice(&env, vk));
+        client.verify_proof_for_circuit(
+            &vk_id,
+            &Bytes::from_slice(&env, public_inputs),
+            &Bytes::from_slice(&env, proof),
+        );
+    }
+}
+
+/// Registry returning the same VK for every circuit id.
+#[contract]
+struct MockVkRegistry;
+
+#[contractimpl]
+impl MockVkRegistry {
+    pub fn __constructor(env: Env, vk: Bytes) {
+        env.storage().instance().set(&symbol_short!("vk"), &vk);
+    }
+
+    pub fn get_vk(env: Env, _circuit_id: BytesN<32>) -> Option<Bytes> {
+        env.storage().instance().get(&symbol_short!("vk"))
+    }
+}
+
+#[test]
+fn verify_with_a_vk_from_a_registry_contract() {
+    let vk_bytes_raw: &[u8] = include_bytes!("simple_circuit/target/vk");
+    let proof_bin: &[u8] = include_bytes!("simple_circuit/target/proof");
+    let pub_inputs_bin: &[u8] = include_bytes!("simple_circuit/target/public_inputs");
+
+    let env = Env::default();
+    env.cost_estimate().budget().reset_unlimited();
+    let registry = env.register(MockVkRegistry, (Bytes::from_slice(&env, vk_bytes_raw),));
+    // The stored VK is unrelated; only the registry's is used.
+    let client = register_client(
+        &env,
+        &Bytes::from_slice(&env, include_bytes!("fib_chain/target/vk")),
+    );
+    client.verify_proof_with_registry(
+        &registry,
+        &BytesN::from_array(&env, &[1; 32]),
+        &Bytes::from_slice(&env, pub_inputs_bin),
+        &Bytes::from_slice(&env, proof_bin),
+    );
+}
+
+#[test]
+fn compressed_vk_verifies_the_fixture() {
+    let vk_bytes_raw: &[u8] = include_bytes!("simple_circuit/target/vk");
+    let proof_bin: &[u8] = include_bytes!("simple_circuit/target/proof");
+    let pub_inputs_bin: &[u8] = include_bytes!("simple_circuit/target/public_inputs");
+
+    let env = Env::default();
+    env.mock_all_auths();
+    env.cost_estimate().budget().reset_unlimited();
+    let vk_bytes = Bytes::from_slice(&env, vk_bytes_raw);
+    let client = register_client(&env, &vk_bytes);
+    let packed = Bytes::from_slice(&env, &zero_rle_compress(vk_bytes_raw));
+
+    client.set_vk_compressed(&packed, &0);
+    assert_eq!(client.stored_vk_id(), client.vk_id(&vk_bytes));
+    client.verify_proof(
+        &Bytes::from_slice(&env, pub_inputs_bin),
+        &Bytes::from_slice(&env, proof_bin),
+    );
+}
+
+#[test]
+fn rotate_vk_installs_a_vk_backed_by_a_migration_proof() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.cost_estimate().budget().reset_unlimited();
+    let old_vk = Bytes::from_slice(&env, include_bytes!("simple_circuit/target/vk"));
+    let new_vk = Bytes::from_slice(&env, include_bytes!("fib_chain/target/vk"));
+    let proof = Bytes::from_slice(&env, include_bytes!("fib_chain/target/proof"));
+    let public_inputs = Bytes::from_slice(&env, include_bytes!("fib_chain/target/public_inputs"));
+    let client = register_client(&env, &old_vk);
+
+    assert_eq!(client.rotate_vk(&new_vk, &proof, &public_inputs, &0), 1);
+    assert_eq!(client.stored_vk_id(), client.vk_id(&new_vk));
+    client.verify_proof(&public_inputs, &proof);
+}
+
+#[test]
+fn committed_transcript_digest_is_stable_for_the_fixture() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.cost_estimate().budget().reset_unlimited();
+    let vk = Bytes::from_slice(&env, include_bytes!("simple_circuit/target/vk"));
+    let proof = Bytes::from_slice(&env, include_bytes!("simple_circuit/target/proof"));
+    let public_inputs =
+        Bytes::from_slice(&env, include_bytes!("simple_circuit/target/public_inputs"));
+    let client = register_client(&env, &vk);
+
+    let digest = client.verify_and_commit_transcript(&public_inputs, &proof);
+    assert_eq!(
+        client.verify_and_commit_transcript(&public_inputs, &proof),
+        digest
+    );
+    // Other public inputs change every challenge, so the proof fails.
+    let mut other = public_inputs.clone();
+    other.set(31, other.get(31).unwrap() ^ 1);
+    assert_eq!(
+        client.try_verify_and_commit_transcript(&other, &proof),
+        Err(Ok(ultrahonk_contract::Error::VerificationFailed))
+    );
+}
+
+#[test]
+fn verify_and_record_returns_a_stable_proof_id() {
+    let vk_bytes_raw: &[u8] = include_bytes!("simple_circuit/target/vk");
+    let proof_bin: &[u8] = include_bytes!("simple_circuit/target/proof");
+    let pub_inputs_bin: &[u8] = include_bytes!("simple_circuit/target/public_inputs");
+
+    let env = Env::default();
+    env.cost_estimate().budget().reset_unlimited();
+    let proof_bytes = Bytes::from_slice(&env, proof_bin);
+    let public_inputs = Bytes::from_slice(&env, pub_inputs_bin);
+    let client = register_client(&env, &Bytes::from_slice(&env, vk_bytes_raw));
+
+    let id = client.verify_and_record(&public_inputs, &proof_bytes);
+    assert_eq!(id, client.proof_id(&public_inputs, &proof_bytes));
+    assert_eq!(client.verify_and_record(&public_inputs, &proof_bytes), id);
+    assert!(client.is_verified(&id));
+    assert!(!client.is_verified(&BytesN::from_array(&env, &[0x42; 32])));
+}
+
+#[test]
+fn verify_and_consume_rejects_a_second_use_of_the_key() {
+    let vk_bytes_raw: &[u8] = include_bytes!("simple_circuit/target/vk");
+    let proof_bin: &[u8] = include_bytes!("simple_circuit/target/proof");
+    let pub_inputs_bin: &[u8] = include_bytes!("simple_circuit/target/public_inputs");
+
+    let env = Env::default();
+    env.cost_estimate().budget().reset_unlimited();
+    let vk_bytes = Bytes::from_slice(&env, vk_bytes_raw);
+    let proof_bytes: Bytes = Bytes::from_slice(&env, proof_bin);
+    let public_inputs: Bytes = Bytes::from_slice(&env, pub_inputs_bin);
+    let nullifier: BytesN<32> = public_inputs.slice(0..32).try_into().unwrap();
+
+    let client = register_client(&env, &vk_bytes);
+    assert!(!client.is_consumed(&nullifier));
+    client.verify_and_consume(&public_inputs, &proof_bytes, &nullifier);
+    assert!(client.is_consumed(&nullifier));
+
+    // Same proof, same key: the double spend is rejected.
+    let err = client.try_verify_and_consume(&public_inputs, &proof_bytes, &nullifier);
+    assert_eq!(err, Err(Ok(ultrahonk_contract::Error::KeyAlreadyConsumed)));
+
+    // Same proof, fresh key: the key is not the proof's, so it is rejected.
+    let other = BytesN::from_array(&env, &[0x43; 32]);
+    let err = client.try_verify_and_consume(&public_inputs, &proof_bytes, &other);
+    assert_eq!(err, Err(Ok(ultrahonk_contract::Error::KeyMismatch)));
+    assert!(!client.is_consumed(&other));
+}
+
+#[test]
+fn deferred_pairing_settles_a_valid_proof() {
+    let vk_bytes_raw: &[u8] = include_bytes!("simple_circuit/target/vk");
+    let proof_bin: &[u8] = include_bytes!("simple_circuit/target/proof");
+    let pub_inputs_bin: &[u8] = include_bytes!("simple_circuit/target/public_inputs");
+
+    let env = Env::default();
+    env.cost_estimate().budget().reset_unlimited();
+    env.mock_all_auths();
+    let vk_bytes = Bytes::from_slice(&env, vk_bytes_raw);
+    let proof_bytes: Bytes = Bytes::from_slice(&env, proof_bin);
+    let public_inputs: Bytes = Bytes::from_slice(&env, pub_inputs_bin);
+
+    let client = register_client(&env, &vk_bytes);
+    client.set_pairing_oracle(&Address::generate(&env));
+    let submitter = Address::generate(&env);
+    let id =
+        client.verify_proof_deferred_pairing(&submitter, &vk_bytes, &public_inputs, &proof_bytes);
+    let pending = client.pending_pairing(&id).unwrap();
+    assert_eq!(pending.binding.vk_id, client.vk_id(&vk_bytes));
+    assert_eq!(pending.binding.submitter, submitter);
+    assert_eq!(
+        client.try_pairing_result(&id),
+        Err(Ok(ultrahonk_contract::Error::PairingPending))
+    );
+
+    // The oracle pairs the stored points off-chain and reports back.
+    client.finalize_pairing(&id, &true);
+    assert!(client.pending_pairing(&id).is_none());
+    let binding = client.pairing_result(&id);
+    assert_eq!(binding, pending.binding);
+    assert_eq!(
+        binding.public_inputs_hash,
+        BytesN::from_array(&env, &env.crypto().keccak256(&public_inputs).to_array())
+    );
+}
+
 #[test]
 fn print_budget_for_deploy_and_verify() {
     let vk_bytes_raw: &[u8] = include_bytes!("simple_circuit/target/vk");
@@ -77,4 +323,11 @@ fn print_budget_for_deploy_and_verify() {
     client.verify_proof(&public_inputs, &proof_bytes);
     println!("=== verify_proof budget usage ===");
     env.cost_estimate().budget().print();
+    // Proof and VK are each copied out of host memory once and parsed in place,
+    // so memory should stay close to the proof/VK sizes rather than per-word slices.
+    println!(
+        "verify_proof totals: cpu = {}, mem = {}",
+        env.cost_estimate().budget().cpu_instruction_cost(),
+        env.cost_estimate().budget().memory_bytes_cost()
+    );
 }
@@ -2,6 +2,7 @@ use num_bigint::BigUint;
 use soroban_poseidon::{poseidon2_hash, Field};
 use soroban_sdk::{crypto::BnScalar, Bytes, Env, U256, Vec as SorobanVec};
 use std::{env, fs, path::Path};
+use tornado_classic_contracts::mixer::leaf_path_bits;
 
 const TREE_DEPTH: usize = 20;
 const DEFAULT_SEED: u64 = 1;
@@ -203,10 +204,13 @@ fn main() {
     let nf = field_hash2(&env, &nullifier, &BigUint::from(0u32));
     let root = compute_root(&env, &leaf, &siblings, &bits);
 
-    let mut path_index = BigUint::from(0u32);
-    for (i, &b) in bits.iter().enumerate() {
-        if b == 1 { path_index += BigUint::from(1u128) << i; }
-    }
+    assert!(bits.len() <= 32, "path_bits longer than 32 levels");
+    let path_index = bits.iter().rev().fold(0u32, |acc, &b| (acc << 1) | u32::from(b));
+    assert_eq!(
+        leaf_path_bits(path_index, bits.len() as u32).as_deref(),
+        Some(&bits[..]),
+        "path_bits must be 0/1 and match the contract's leaf path"
+    );
     // append updated fields at end (simple and explicit)
     let mut out = String::new();
     out.push_str(&base_content);
@@ -3,7 +3,7 @@ extern crate alloc;
 use alloc::vec::Vec;
 use soroban_poseidon::{poseidon2_hash, Field};
 use soroban_sdk::{
-    contract, contracterror, contractevent, contractimpl, crypto::BnScalar, symbol_short, Address,
+    contract, contracterror, contractevent, contractimpl, contracttype, crypto::BnScalar, symbol_short, Address,
     Bytes, BytesN, Env, InvokeError, IntoVal, Symbol, U256, Vec as SorobanVec, Val,
 };
 use ultrahonk_soroban_verifier::PROOF_BYTES;
@@ -22,18 +22,71 @@ pub enum MixerError {
     VerifierNotSet = 5,
     TreeFull = 6,
     RootNotSet = 7,
+    EmptyBatch = 8,
+    AdminNotConfigured = 9,
+    UnsupportedProtocol = 10,
+    DepositVerifierNotSet = 11,
+    SchemaMismatch = 12,
+    RecipientNotAllowed = 13,
+    NoteTooLarge = 14,
 }
 
+/// Word indices of the values `withdraw` reads from the public inputs, for
+/// circuits with extra inputs (fee, relayer, ...). Defaults to the shipped
+/// circuit's `[root, nullifier_hash]`, which has no recipient.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PublicInputSchema {
+    pub root: u32,
+    pub nullifier_hash: u32,
+    /// Needed only while a recipient allowlist is set.
+    pub recipient: Option<u32>,
+}
+
+impl PublicInputSchema {
+    fn default_layout() -> Self {
+        Self {
+            root: 0,
+            nullifier_hash: 1,
+            recipient: None,
+        }
+    }
+}
+
+/// Everything a client needs to size and check a local copy of the tree.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TreeInfo {
+    /// Current root; `None` before the first deposit.
+    pub root: Option<BytesN<32>>,
+    /// Index the next deposit gets, i.e. the number of leaves so far.
+    pub next_index: u32,
+    pub depth: u32,
+    pub max_leaves: u32,
+}
+
+/// Version topic carried by every mixer event. Bump it whenever an event's
+/// topics or data fields change, so indexers can pick the right decoder.
+pub const EVENT_VERSION: u32 = 1;
+
+/// Topics `("deposit", version, idx)`; data is a map with `commitment`.
 #[contractevent(topics = ["deposit"], data_format = "map")]
 pub struct DepositEvent<'a> {
+    #[topic]
+    pub version: &'a u32,
     #[topic]
     pub idx: &'a u32,
     pub commitment: &'a BytesN<32>,
 }
 
-#[contractevent(topics = ["withdraw"], data_format = "single-value")]
+/// Topics `("withdraw", version)`; data is a map with the spent
+/// `nullifier_hash` and the `root` the proof was checked against.
+#[contractevent(topics = ["withdraw"], data_format = "map")]
 pub struct WithdrawEvent<'a> {
+    #[topic]
+    pub version: &'a u32,
     pub nullifier_hash: &'a BytesN<32>,
+    pub root: &'a BytesN<32>,
 }
 
 fn key_commitment_prefix() -> Symbol { symbol_short!("cm") }
@@ -42,9 +95,43 @@ fn key_root() -> Symbol { symbol_short!("root") }
 fn key_frontier_prefix() -> Symbol { symbol_short!("fr") }
 fn key_next_index() -> Symbol { symbol_short!("idx") }
 fn key_verifier() -> Symbol { symbol_short!("ver") }
+fn key_admin() -> Symbol { symbol_short!("admin") }
+fn key_deposit_verifier() -> Symbol { symbol_short!("dver") }
+fn key_schema() -> Symbol { symbol_short!("schema") }
+fn key_leaf_prefix() -> Symbol { symbol_short!("leaf") }
+fn key_anchor_prefix() -> Symbol { symbol_short!("anchor") }
+fn key_allowed_prefix() -> Symbol { symbol_short!("allow") }
+fn key_allowed_count() -> Symbol { symbol_short!("allow_n") }
+fn key_note_prefix() -> Symbol { symbol_short!("note") }
 
 const TREE_DEPTH: u32 = 20;
+/// First protocol version with the Poseidon2 and BN254 host functions.
+const MIN_PROTOCOL_VERSION: u32 = 25;
 const MAX_LEAVES: u32 = 1u32 << TREE_DEPTH;
+/// Most leaves `get_commitments_range` returns per call.
+const MAX_RANGE: u32 = 256;
+/// Ledgers per day at a ~5s close time.
+const DAY_IN_LEDGERS: u32 = 17_280;
+/// TTL the persistent entries (leaves, anchors, notes, allowlisted
+/// recipients) are extended to.
+pub const PERSISTENT_TTL_EXTEND_TO: u32 = 30 * DAY_IN_LEDGERS;
+/// Remaining TTL below which a write extends the entry again.
+const PERSISTENT_TTL_THRESHOLD: u32 = PERSISTENT_TTL_EXTEND_TO - DAY_IN_LEDGERS;
+/// Largest encrypted note `deposit_with_note` stores; room for a 64-byte
+/// `(nullifier, secret)` plus an ephemeral key, nonce and tag.
+pub const MAX_NOTE_BYTES: u32 = 256;
+
+/// Path bits of leaf `index` in a tree of `depth` levels, leaf level first:
+/// bit `i` is 1 when the node at level `i` is a right child. `None` when
+/// `depth > 32` or `index` does not fit in `depth` bits, so callers never
+/// shift past the width of `u32`. `populate_publics` uses the same helper
+/// to turn `path_bits` back into `path_index`.
+pub fn leaf_path_bits(index: u32, depth: u32) -> Option<Vec<u8>> {
+    if depth > 32 || u64::from(index) >= 1u64 << depth {
+        return None;
+    }
+    Some((0..depth).map(|i| ((index >> i) & 1) as u8).collect())
+}
 
 fn poseidon2_hash2(env: &Env, a: &BytesN<32>, b: &BytesN<32>) -> BytesN<32> {
     let modulus = <BnScalar as Field>::modulus(env);
@@ -60,6 +147,16 @@ fn poseidon2_hash2(env: &Env, a: &BytesN<32>, b: &BytesN<32>) -> BytesN<32> {
     BytesN::from_array(env, &out_arr)
 }
 
+/// Host hashing traps on older protocols; report it as an error instead.
+// `protocol_version` is deprecated, but nothing else tells us up front.
+#[allow(deprecated)]
+fn require_host_crypto(env: &Env) -> Result<(), MixerError> {
+    if env.ledger().protocol_version() < MIN_PROTOCOL_VERSION {
+        return Err(MixerError::UnsupportedProtocol);
+    }
+    Ok(())
+}
+
 fn zeroes_for_tree(env: &Env) -> Vec<BytesN<32>> {
     // zero[0] = 0; zero[i+1] = H(zero[i], zero[i])
     let mut zeroes = Vec::with_capacity(TREE_DEPTH as usize + 1);
@@ -72,17 +169,56 @@ fn zeroes_for_tree(env: &Env) -> Vec<BytesN<32>> {
     zeroes
 }
 
-fn parse_public_inputs(bytes: &Bytes) -> Result<([u8; 32], [u8; 32]), MixerError> {
-    if bytes.len() != 64 {
+/// Store an entry that accumulates over time in persistent storage, so it
+/// never counts against the size-capped instance entry, and extend its TTL.
+fn set_persistent<K, V>(env: &Env, key: &K, value: &V)
+where
+    K: IntoVal<Env, Val>,
+    V: IntoVal<Env, Val>,
+{
+    let persistent = env.storage().persistent();
+    persistent.set(key, value);
+    persistent.extend_ttl(key, PERSISTENT_TTL_THRESHOLD, PERSISTENT_TTL_EXTEND_TO);
+}
+
+fn require_admin(env: &Env) -> Result<(), MixerError> {
+    let admin: Address = env
+        .storage()
+        .instance()
+        .get(&key_admin())
+        .ok_or(MixerError::AdminNotConfigured)?;
+    admin.require_auth();
+    Ok(())
+}
+
+fn read_word(bytes: &Bytes, index: u32) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    bytes
+        .slice(index * 32..(index + 1) * 32)
+        .copy_into_slice(&mut word);
+    word
+}
+
+/// Reads `(root, nullifier_hash, recipient)` at the schema's indices.
+fn parse_public_inputs(
+    bytes: &Bytes,
+    schema: &PublicInputSchema,
+) -> Result<([u8; 32], [u8; 32], Option<[u8; 32]>), MixerError> {
+    if bytes.len() % 32 != 0 {
         return Err(MixerError::VerificationFailed);
     }
-    let mut buf = [0u8; 64];
-    bytes.copy_into_slice(&mut buf);
-    let mut root = [0u8; 32];
-    root.copy_from_slice(&buf[..32]);
-    let mut nullifier_hash = [0u8; 32];
-    nullifier_hash.copy_from_slice(&buf[32..]);
-    Ok((root, nullifier_hash))
+    let last = schema
+        .root
+        .max(schema.nullifier_hash)
+        .max(schema.recipient.unwrap_or(0));
+    if bytes.len() / 32 <= last {
+        return Err(MixerError::SchemaMismatch);
+    }
+    Ok((
+        read_word(bytes, schema.root),
+        read_word(bytes, schema.nullifier_hash),
+        schema.recipient.map(|i| read_word(bytes, i)),
+    ))
 }
 
 fn verify_proof(
@@ -101,14 +237,36 @@ fn verify_proof(
 
 #[contractimpl]
 impl MixerContract {
-    /// Initialize the contract with the verifier address.
-    pub fn __constructor(env: Env, verifier: Address) -> Result<(), MixerError> {
+    /// Initialize the contract with the admin and the verifier address.
+    pub fn __constructor(env: Env, admin: Address, verifier: Address) -> Result<(), MixerError> {
+        env.storage().instance().set(&key_admin(), &admin);
         env.storage().instance().set(&key_verifier(), &verifier);
         Ok(())
     }
 
+    /// Hands the admin role to `new_admin`; requires the current admin's auth.
+    pub fn transfer_admin(env: Env, new_admin: Address) -> Result<(), MixerError> {
+        require_admin(&env)?;
+        env.storage().instance().set(&key_admin(), &new_admin);
+        Ok(())
+    }
+
+    /// Clears the admin for good; admin-gated calls fail with
+    /// `AdminNotConfigured` afterwards. Requires the current admin's auth.
+    pub fn renounce_admin(env: Env) -> Result<(), MixerError> {
+        require_admin(&env)?;
+        env.storage().instance().remove(&key_admin());
+        Ok(())
+    }
+
+    /// Returns the current admin, if one is configured.
+    pub fn admin(env: Env) -> Option<Address> {
+        env.storage().instance().get(&key_admin())
+    }
+
     /// Inserts a new leaf into the Poseidon2 Merkle tree and returns its index.
     pub fn deposit(env: Env, commitment: BytesN<32>) -> Result<u32, MixerError> {
+        require_host_crypto(&env)?;
         let cm_key = (key_commitment_prefix(), commitment.clone());
         if env.storage().instance().has(&cm_key) {
             return Err(MixerError::CommitmentExists);
@@ -125,17 +283,20 @@ impl MixerContract {
         }
         let idx = next_index;
         env.storage().instance().set(&cm_key, &true);
+        set_persistent(&env, &(key_leaf_prefix(), idx), &commitment);
         DepositEvent {
+            version: &EVENT_VERSION,
             idx: &idx,
             commitment: &commitment,
         }
         .publish(&env);
         // leaf index used for insertion
         let ins_idx = next_index;
+        let bits = leaf_path_bits(ins_idx, TREE_DEPTH).ok_or(MixerError::TreeFull)?;
         let mut cur = commitment.clone();
         let mut i = 0u32;
         while i < TREE_DEPTH {
-            let bit = (ins_idx >> i) & 1;
+            let bit = bits[i as usize];
             if bit == 0 {
                 // save left sibling at this level, pair with zero
                 let fk = (key_frontier_prefix(), i);
@@ -162,18 +323,260 @@ impl MixerContract {
         Ok(idx)
     }
 
-    /// Verifies a proof with the stored verification key and marks the nullifier spent.
-    /// The public inputs are ordered as `[root, nullifier_hash]`.
-    pub fn withdraw(
+    /// Like `deposit`, but also stores `encrypted_note` under the commitment
+    /// so a depositor who loses the note can recover it with
+    /// `get_encrypted_note`. The blob is encrypted client-side and opaque
+    /// here; it is not part of the tree. Fails with `NoteTooLarge` above
+    /// `MAX_NOTE_BYTES`, before anything is deposited.
+    pub fn deposit_with_note(
         env: Env,
-        public_inputs: Bytes,
+        commitment: BytesN<32>,
+        encrypted_note: Bytes,
+    ) -> Result<u32, MixerError> {
+        if encrypted_note.len() > MAX_NOTE_BYTES {
+            return Err(MixerError::NoteTooLarge);
+        }
+        let idx = Self::deposit(env.clone(), commitment.clone())?;
+        // Persistent rather than instance storage: notes accumulate per deposit.
+        set_persistent(&env, &(key_note_prefix(), commitment), &encrypted_note);
+        Ok(idx)
+    }
+
+    /// The encrypted note stored by `deposit_with_note`, if any.
+    pub fn get_encrypted_note(env: Env, commitment: BytesN<32>) -> Option<Bytes> {
+        env.storage()
+            .persistent()
+            .get(&(key_note_prefix(), commitment))
+    }
+
+    /// Sets the verifier contract `deposit_checked` uses; admin only.
+    ///
+    /// Its VK must be for a deposit circuit whose only public input is the
+    /// commitment and which proves knowledge of `(nullifier, secret)` with
+    /// `commitment == H(nullifier, secret)`.
+    pub fn set_deposit_verifier(env: Env, verifier: Address) -> Result<(), MixerError> {
+        require_admin(&env)?;
+        env.storage()
+            .instance()
+            .set(&key_deposit_verifier(), &verifier);
+        Ok(())
+    }
+
+    /// Like `deposit`, but first requires a proof that `commitment` is a
+    /// well-formed note, so no leaf is spent on a commitment that can never
+    /// be withdrawn.
+    ///
+    /// Trust assumptions: soundness rests entirely on the deposit verifier
+    /// and its VK, which the admin chooses. The proof's only public input is
+    /// the commitment; the nullifier hash is deliberately not revealed here,
+    /// since publishing it at deposit time would link the deposit to its
+    /// withdrawal. Plain `deposit` remains available and unchecked.
+    pub fn deposit_checked(
+        env: Env,
+        commitment: BytesN<32>,
         proof_bytes: Bytes,
-    ) -> Result<(), MixerError> {
+    ) -> Result<u32, MixerError> {
         if proof_bytes.len() as usize != PROOF_BYTES {
             return Err(MixerError::VerificationFailed);
         }
-        // Interpret public inputs as `[root, nullifier_hash]`.
-        let (root_arr, nf_arr) = parse_public_inputs(&public_inputs)?;
+        let verifier: Address = env
+            .storage()
+            .instance()
+            .get(&key_deposit_verifier())
+            .ok_or(MixerError::DepositVerifierNotSet)?;
+        let public_inputs = Bytes::from_array(&env, &commitment.to_array());
+        verify_proof(&env, &verifier, public_inputs, proof_bytes)?;
+        Self::deposit(env, commitment)
+    }
+
+    /// Inserts several leaves at once and returns the resulting root.
+    ///
+    /// Leaves are hashed level by level, so nodes shared by the batch are
+    /// computed once instead of once per leaf; the root and frontier end up
+    /// identical to calling `deposit` for each commitment in order. The whole
+    /// batch is rejected if any commitment repeats, within the batch or
+    /// against earlier deposits.
+    pub fn deposit_batch(
+        env: Env,
+        commitments: soroban_sdk::Vec<BytesN<32>>,
+    ) -> Result<BytesN<32>, MixerError> {
+        require_host_crypto(&env)?;
+        let count = commitments.len();
+        if count == 0 {
+            return Err(MixerError::EmptyBatch);
+        }
+        let next_index: u32 = env
+            .storage()
+            .instance()
+            .get(&key_next_index())
+            .unwrap_or(0u32);
+        if count > MAX_LEAVES - next_index.min(MAX_LEAVES) {
+            return Err(MixerError::TreeFull);
+        }
+        let mut nodes: Vec<BytesN<32>> = Vec::with_capacity(count as usize);
+        for commitment in commitments.iter() {
+            let cm_key = (key_commitment_prefix(), commitment.clone());
+            if env.storage().instance().has(&cm_key) || nodes.contains(&commitment) {
+                return Err(MixerError::CommitmentExists);
+            }
+            nodes.push(commitment);
+        }
+        for (offset, commitment) in nodes.iter().enumerate() {
+            let idx = next_index + offset as u32;
+            env.storage()
+                .instance()
+                .set(&(key_commitment_prefix(), commitment.clone()), &true);
+            set_persistent(&env, &(key_leaf_prefix(), idx), commitment);
+            DepositEvent {
+                version: &EVENT_VERSION,
+                idx: &idx,
+                commitment,
+            }
+            .publish(&env);
+        }
+
+        // `nodes` holds the positions `lo..=hi` of the current level.
+        let zeroes = zeroes_for_tree(&env);
+        let mut lo = next_index;
+        let mut hi = next_index + count - 1;
+        for i in 0..TREE_DEPTH {
+            let fk = (key_frontier_prefix(), i);
+            // An odd `lo` pairs with the left sibling saved by earlier deposits.
+            let saved_left: Option<BytesN<32>> = if lo & 1 == 1 {
+                env.storage().instance().get(&fk)
+            } else {
+                None
+            };
+            // The last left child on this level is what sequential inserts
+            // would have left in the frontier.
+            let last_left = hi & !1;
+            if last_left >= lo {
+                env.storage()
+                    .instance()
+                    .set(&fk, &nodes[(last_left - lo) as usize]);
+            }
+            let mut parents = Vec::with_capacity(nodes.len() / 2 + 1);
+            for q in (lo >> 1)..=(hi >> 1) {
+                let (l, r) = (2 * q, 2 * q + 1);
+                let left = if l >= lo {
+                    nodes[(l - lo) as usize].clone()
+                } else {
+                    saved_left
+                        .clone()
+                        .unwrap_or_else(|| zeroes[i as usize].clone())
+                };
+                let right = if r <= hi {
+                    nodes[(r - lo) as usize].clone()
+                } else {
+                    zeroes[i as usize].clone()
+                };
+                parents.push(poseidon2_hash2(&env, &left, &right));
+            }
+            nodes = parents;
+            lo >>= 1;
+            hi >>= 1;
+        }
+        let root = nodes[0].clone();
+        env.storage().instance().set(&key_root(), &root);
+        env.storage()
+            .instance()
+            .set(&key_next_index(), &(next_index + count));
+        Ok(root)
+    }
+
+    /// Sets where `withdraw` finds the root and nullifier hash; admin only.
+    /// The verifier's VK must be for a circuit with that layout.
+    pub fn set_public_input_schema(env: Env, schema: PublicInputSchema) -> Result<(), MixerError> {
+        require_admin(&env)?;
+        if schema.root == schema.nullifier_hash
+            || schema.recipient == Some(schema.root)
+            || schema.recipient == Some(schema.nullifier_hash)
+        {
+            return Err(MixerError::SchemaMismatch);
+        }
+        env.storage().instance().set(&key_schema(), &schema);
+        Ok(())
+    }
+
+    /// Returns the layout `withdraw` reads public inputs with.
+    pub fn public_input_schema(env: Env) -> PublicInputSchema {
+        env.storage()
+            .instance()
+            .get(&key_schema())
+            .unwrap_or_else(PublicInputSchema::default_layout)
+    }
+
+    /// Adds `recipient` (the 32-byte public input, as the circuit encodes it)
+    /// to the withdrawal allowlist; admin only. While the list is empty,
+    /// anyone may receive.
+    ///
+    /// Each entry is its own persistent entry, so the list can grow without
+    /// bloating the instance; only the count lives there.
+    pub fn allow_recipient(env: Env, recipient: BytesN<32>) -> Result<(), MixerError> {
+        require_admin(&env)?;
+        let key = (key_allowed_prefix(), recipient);
+        if !env.storage().persistent().has(&key) {
+            set_persistent(&env, &key, &true);
+            let count = Self::allowed_recipient_count(env.clone());
+            env.storage()
+                .instance()
+                .set(&key_allowed_count(), &(count + 1));
+        }
+        Ok(())
+    }
+
+    /// Removes `recipient` from the allowlist; admin only. Removing the last
+    /// entry makes withdrawals permissionless again.
+    pub fn revoke_recipient(env: Env, recipient: BytesN<32>) -> Result<(), MixerError> {
+        require_admin(&env)?;
+        let key = (key_allowed_prefix(), recipient);
+        if env.storage().persistent().has(&key) {
+            env.storage().persistent().remove(&key);
+            let count = Self::allowed_recipient_count(env.clone());
+            env.storage()
+                .instance()
+                .set(&key_allowed_count(), &(count - 1));
+        }
+        Ok(())
+    }
+
+    /// Number of allowlisted recipients; 0 means the allowlist is off.
+    pub fn allowed_recipient_count(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&key_allowed_count())
+            .unwrap_or(0)
+    }
+
+    /// Whether `withdraw` would accept `recipient`.
+    pub fn is_recipient_allowed(env: Env, recipient: BytesN<32>) -> bool {
+        Self::allowed_recipient_count(env.clone()) == 0
+            || env
+                .storage()
+                .persistent()
+                .has(&(key_allowed_prefix(), recipient))
+    }
+
+    /// Verifies a proof with the stored verification key and marks the nullifier spent.
+    /// The root and nullifier hash are read at the indices of `public_input_schema`,
+    /// `[root, nullifier_hash]` unless the admin set another layout; fails with
+    /// `SchemaMismatch` if there are too few public inputs for it.
+    /// While the recipient allowlist is set, the schema's recipient must be on
+    /// it, or the call fails with `RecipientNotAllowed`.
+    pub fn withdraw(env: Env, public_inputs: Bytes, proof_bytes: Bytes) -> Result<(), MixerError> {
+        if proof_bytes.len() as usize != PROOF_BYTES {
+            return Err(MixerError::VerificationFailed);
+        }
+        let schema = Self::public_input_schema(env.clone());
+        let (root_arr, nf_arr, recipient) = parse_public_inputs(&public_inputs, &schema)?;
+        if Self::allowed_recipient_count(env.clone()) > 0 {
+            let allowed = recipient.is_some_and(|r| {
+                Self::is_recipient_allowed(env.clone(), BytesN::from_array(&env, &r))
+            });
+            if !allowed {
+                return Err(MixerError::RecipientNotAllowed);
+            }
+        }
         let nf_from_proof = BytesN::from_array(&env, &nf_arr);
         // Nullifier indicates a spent note; fail if already seen.
         let nf_key = (key_nullifier_prefix(), nf_from_proof.clone());
@@ -200,12 +603,34 @@ impl MixerContract {
         // Mark nullifier as spent and emit withdraw event containing nullifier hash.
         env.storage().instance().set(&nf_key, &true);
         WithdrawEvent {
+            version: &EVENT_VERSION,
             nullifier_hash: &nf_from_proof,
+            root: &root_from_proof,
         }
         .publish(&env);
         Ok(())
     }
 
+    /// Runs `verifier` on the proof and returns the root it commits to (at
+    /// the schema's `root` index, 0 by default), without checking it against
+    /// the stored root or touching nullifier state. For callers that keep
+    /// their own set of acceptable roots and spend logic; which verifier to
+    /// trust is theirs to decide.
+    pub fn verify_only(
+        env: Env,
+        verifier: Address,
+        public_inputs: Bytes,
+        proof_bytes: Bytes,
+    ) -> Result<BytesN<32>, MixerError> {
+        if proof_bytes.len() as usize != PROOF_BYTES {
+            return Err(MixerError::VerificationFailed);
+        }
+        let schema = Self::public_input_schema(env.clone());
+        let (root_arr, _, _) = parse_public_inputs(&public_inputs, &schema)?;
+        verify_proof(&env, &verifier, public_inputs, proof_bytes)?;
+        Ok(BytesN::from_array(&env, &root_arr))
+    }
+
     /// Returns true if the nullifier hash has already been consumed.
     pub fn is_nullifier_used(env: Env, nullifier_hash: BytesN<32>) -> bool {
         let nf_key = (key_nullifier_prefix(), nullifier_hash);
@@ -217,13 +642,70 @@ impl MixerContract {
         env.storage().instance().get(&key_root())
     }
 
+    /// Records the current root against the current ledger sequence and
+    /// returns both, so others can later check what the root was then.
+    /// Anchoring twice in one ledger records the same root.
+    pub fn anchor_root(env: Env) -> Result<(BytesN<32>, u32), MixerError> {
+        let root: BytesN<32> = env
+            .storage()
+            .instance()
+            .get(&key_root())
+            .ok_or(MixerError::RootNotSet)?;
+        let seq = env.ledger().sequence();
+        set_persistent(&env, &(key_anchor_prefix(), seq), &root);
+        Ok((root, seq))
+    }
+
+    /// Returns the root anchored at ledger `seq`, if any.
+    pub fn get_anchor(env: Env, seq: u32) -> Option<BytesN<32>> {
+        env.storage().persistent().get(&(key_anchor_prefix(), seq))
+    }
+
+    /// Returns the leaf at `index`, if one has been deposited there.
+    pub fn get_commitment_by_index(env: Env, index: u32) -> Option<BytesN<32>> {
+        env.storage().persistent().get(&(key_leaf_prefix(), index))
+    }
+
+    /// Returns the root, leaf count and shape of the tree in one read.
+    pub fn tree_info(env: Env) -> TreeInfo {
+        TreeInfo {
+            root: env.storage().instance().get(&key_root()),
+            next_index: env
+                .storage()
+                .instance()
+                .get(&key_next_index())
+                .unwrap_or(0u32),
+            depth: TREE_DEPTH,
+            max_leaves: MAX_LEAVES,
+        }
+    }
+
+    /// Returns the leaves at indices `start..end`, clamped to those deposited
+    /// so far and to at most 256 per call; page through larger ranges.
+    pub fn get_commitments_range(env: Env, start: u32, end: u32) -> soroban_sdk::Vec<BytesN<32>> {
+        let next_index: u32 = env
+            .storage()
+            .instance()
+            .get(&key_next_index())
+            .unwrap_or(0u32);
+        let end = end.min(next_index).min(start.saturating_add(MAX_RANGE));
+        let mut leaves = SorobanVec::new(&env);
+        for idx in start..end {
+            if let Some(leaf) = env.storage().persistent().get(&(key_leaf_prefix(), idx)) {
+                leaves.push_back(leaf);
+            }
+        }
+        leaves
+    }
 }
 
 #[cfg(any(test, feature = "testutils"))]
 #[contractimpl]
 impl MixerContract {
     /// Test-only helper to override the stored root when running under debug builds.
+    /// Admin only.
     pub fn set_root(env: Env, root: BytesN<32>) -> Result<(), MixerError> {
+        require_admin(&env)?;
         env.storage().instance().set(&key_root(), &root);
         Ok(())
     }
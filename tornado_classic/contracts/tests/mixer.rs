@@ -1,13 +1,17 @@
 use soroban_env_host::DiagnosticLevel;
 use soroban_poseidon::{poseidon2_hash, Field};
 use soroban_sdk::{
-    crypto::BnScalar, testutils::Address as TestAddress, Address, Bytes, BytesN, Env, U256,
-    Vec as SorobanVec,
+    contract, contracterror, contractimpl, crypto::BnScalar, symbol_short,
+    testutils::{storage::Persistent as _, Address as TestAddress, Events, Ledger}, Address, Bytes,
+    BytesN, Env, Event, U256, Vec as SorobanVec,
 };
 
 use std::sync::{Mutex, OnceLock};
 
-use tornado_classic_contracts::mixer::{MixerContract, MixerError};
+use tornado_classic_contracts::mixer::{
+    DepositEvent, MixerContract, MixerContractClient, MixerError, PublicInputSchema, TreeInfo,
+    WithdrawEvent, EVENT_VERSION, MAX_NOTE_BYTES, PERSISTENT_TTL_EXTEND_TO, leaf_path_bits,
+};
 use rs_soroban_ultrahonk::UltraHonkVerifierContract;
 use ultrahonk_soroban_verifier::PROOF_BYTES;
 
@@ -96,10 +100,15 @@ fn frontier_root_from_leaves(env: &Env, leaves: &[[u8; 32]], depth: u32) -> [u8;
 }
 
 fn register_verifier(env: &Env, vk_bytes: &Bytes) -> Address {
-    env.register(UltraHonkVerifierContract, (vk_bytes.clone(),))
+    let admin = <Address as TestAddress>::generate(env);
+    env.register(
+        UltraHonkVerifierContract,
+        (admin, vk_bytes.clone(), SorobanVec::<Bytes>::new(env)),
+    )
 }
 fn register_mixer(env: &Env, verifier: Address) -> Address {
-    env.register(MixerContract, (verifier,))
+    let admin = <Address as TestAddress>::generate(env);
+    env.register(MixerContract, (admin, verifier))
 }
 
 #[cfg(feature = "wasm-cost")]
@@ -107,7 +116,11 @@ fn register_wasm_verifier<'a>(
     env: &'a Env,
     vk_bytes: &Bytes,
 ) -> (wasm_artifacts::ultrahonk_contract::Client<'a>, Address) {
-    let contract_id = env.register(wasm_artifacts::VERIFIER_WASM, (vk_bytes.clone(),));
+    let admin = <Address as TestAddress>::generate(env);
+    let contract_id = env.register(
+        wasm_artifacts::VERIFIER_WASM,
+        (admin, vk_bytes.clone(), SorobanVec::<Bytes>::new(env)),
+    );
     (wasm_artifacts::ultrahonk_contract::Client::new(env, &contract_id), contract_id)
 }
 
@@ -116,7 +129,8 @@ fn register_wasm_mixer<'a>(
     env: &'a Env,
     verifier: Address,
 ) -> (wasm_artifacts::mixer_contract::Client<'a>, Address) {
-    let contract_id = env.register(wasm_artifacts::MIXER_WASM, (verifier,));
+    let admin = <Address as TestAddress>::generate(env);
+    let contract_id = env.register(wasm_artifacts::MIXER_WASM, (admin, verifier));
     (wasm_artifacts::mixer_contract::Client::new(env, &contract_id), contract_id)
 }
 
@@ -145,12 +159,41 @@ fn merkle_frontier_updates_root_matches_reference() {
     }
 }
 
+/// `leaf_path_bits` matches the `(index >> i) & 1` walk the reference tree
+/// uses, reassembles to the index, and rejects indices outside the tree.
+#[test]
+fn leaf_path_bits_agree_with_the_reference_walk() {
+    for depth in [0u32, 1, 3, 20, 31, 32] {
+        let max = if depth == 32 { u32::MAX } else { (1u32 << depth) - 1 };
+        for index in [0u32, 1, 5, max / 2, max] {
+            if u64::from(index) >= 1u64 << depth {
+                continue;
+            }
+            let bits = leaf_path_bits(index, depth).unwrap();
+            assert_eq!(bits.len(), depth as usize);
+            for (i, &b) in bits.iter().enumerate() {
+                assert_eq!(u32::from(b), (index >> i) & 1, "index {index} depth {depth} level {i}");
+            }
+            let back = bits.iter().rev().fold(0u64, |acc, &b| (acc << 1) | u64::from(b));
+            assert_eq!(back, u64::from(index));
+        }
+    }
+    assert_eq!(leaf_path_bits(5, 3), Some(vec![1, 0, 1]));
+    assert_eq!(leaf_path_bits(0, 0), Some(vec![]));
+    assert_eq!(leaf_path_bits(1, 0), None);
+    assert_eq!(leaf_path_bits(8, 3), None);
+    assert_eq!(leaf_path_bits(1 << TREE_DEPTH_TEST, TREE_DEPTH_TEST), None);
+    assert_eq!(leaf_path_bits(0, 33), None);
+}
+
 /// Happy-path withdraw followed by a double-spend attempt confirms the nullifier is enforced.
 #[test]
 #[cfg(feature = "testutils")]
 fn mixer_withdraw_and_double_spend_rejected() {
     let _guard = verify_lock().lock().unwrap();
     let env = Env::default();
+    // set_root is admin-gated.
+    env.mock_all_auths();
     env.cost_estimate().budget().reset_unlimited();
     let _ = env.host().set_diagnostic_level(DiagnosticLevel::None);
 
@@ -201,6 +244,8 @@ fn mixer_withdraw_and_double_spend_rejected() {
 #[cfg(feature = "testutils")]
 fn set_root_overrides_root() {
     let env = Env::default();
+    // set_root is admin-gated.
+    env.mock_all_auths();
     let _ = env.host().set_diagnostic_level(DiagnosticLevel::None);
     let verifier_id = <Address as TestAddress>::generate(&env);
     let mixer_id: Address = register_mixer(&env, verifier_id);
@@ -218,6 +263,8 @@ fn set_root_overrides_root() {
 fn withdraw_rejects_invalid_public_inputs() {
     let _guard = verify_lock().lock().unwrap();
     let env = Env::default();
+    // set_root is admin-gated.
+    env.mock_all_auths();
     env.cost_estimate().budget().reset_unlimited();
     let _ = env.host().set_diagnostic_level(DiagnosticLevel::None);
 
@@ -268,6 +315,8 @@ fn withdraw_rejects_invalid_public_inputs() {
 fn withdraw_rejects_root_mismatch() {
     let _guard = verify_lock().lock().unwrap();
     let env = Env::default();
+    // set_root is admin-gated.
+    env.mock_all_auths();
     env.cost_estimate().budget().reset_unlimited();
     let _ = env.host().set_diagnostic_level(DiagnosticLevel::None);
 
@@ -313,6 +362,8 @@ fn withdraw_rejects_root_mismatch() {
 fn print_wasm_budget_for_deposit_and_withdraw() {
     let _guard = verify_lock().lock().unwrap();
     let env = Env::default();
+    // set_root is admin-gated.
+    env.mock_all_auths();
     env.cost_estimate().budget().reset_unlimited();
     let _ = env.host().set_diagnostic_level(DiagnosticLevel::None);
 
@@ -362,3 +413,597 @@ fn deposit_rejects_duplicate_commitment() {
         .expect("expected duplicate commitment error");
     assert_eq!(err as u32, MixerError::CommitmentExists as u32);
 }
+
+/// A batch insert yields the same root (and frontier) as depositing the leaves one by one.
+#[test]
+fn deposit_batch_matches_sequential_deposits() {
+    let env = Env::default();
+    env.cost_estimate().budget().reset_unlimited();
+    let _ = env.host().set_diagnostic_level(DiagnosticLevel::None);
+    let verifier_id = <Address as TestAddress>::generate(&env);
+    let sequential_id: Address = register_mixer(&env, verifier_id.clone());
+    let batched_id: Address = register_mixer(&env, verifier_id);
+
+    let leaves: Vec<BytesN<32>> = (0u64..6)
+        .map(|i| BytesN::from_array(&env, &be32_from_u64(i + 1)))
+        .collect();
+
+    // Seed one leaf first so the batch starts at an odd index.
+    for id in [&sequential_id, &batched_id] {
+        env.as_contract(id, || MixerContract::deposit(env.clone(), leaves[0].clone()))
+            .unwrap();
+    }
+    for leaf in &leaves[1..5] {
+        env.as_contract(&sequential_id, || MixerContract::deposit(env.clone(), leaf.clone()))
+            .unwrap();
+    }
+    let mut batch = SorobanVec::new(&env);
+    for leaf in &leaves[1..5] {
+        batch.push_back(leaf.clone());
+    }
+    let root = env
+        .as_contract(&batched_id, || MixerContract::deposit_batch(env.clone(), batch))
+        .unwrap();
+    let expected = env
+        .as_contract(&sequential_id, || MixerContract::get_root(env.clone()))
+        .unwrap();
+    assert_eq!(root, expected);
+
+    // The frontier must also line up: a further single deposit agrees too.
+    for id in [&sequential_id, &batched_id] {
+        let idx = env
+            .as_contract(id, || MixerContract::deposit(env.clone(), leaves[5].clone()))
+            .unwrap();
+        assert_eq!(idx, 5);
+    }
+    let a = env.as_contract(&sequential_id, || MixerContract::get_root(env.clone()));
+    let b = env.as_contract(&batched_id, || MixerContract::get_root(env.clone()));
+    assert_eq!(a, b);
+}
+
+/// `tree_info` and `get_commitments_range` agree with the per-leaf getters.
+#[test]
+fn tree_info_and_leaf_range_match_individual_getters() {
+    let env = Env::default();
+    env.cost_estimate().budget().reset_unlimited();
+    let _ = env.host().set_diagnostic_level(DiagnosticLevel::None);
+    let verifier_id = <Address as TestAddress>::generate(&env);
+    let mixer = MixerContractClient::new(&env, &register_mixer(&env, verifier_id));
+
+    let empty = mixer.tree_info();
+    assert_eq!(empty.root, None);
+    assert_eq!(empty.next_index, 0);
+    assert_eq!(empty.max_leaves, 1 << empty.depth);
+
+    let leaves: Vec<BytesN<32>> = (0u64..5)
+        .map(|i| BytesN::from_array(&env, &be32_from_u64(i + 100)))
+        .collect();
+    mixer.deposit(&leaves[0]);
+    let mut batch = SorobanVec::new(&env);
+    for leaf in &leaves[1..4] {
+        batch.push_back(leaf.clone());
+    }
+    mixer.deposit_batch(&batch);
+    mixer.deposit(&leaves[4]);
+
+    assert_eq!(
+        mixer.tree_info(),
+        TreeInfo {
+            root: mixer.get_root(),
+            next_index: 5,
+            depth: TREE_DEPTH_TEST,
+            max_leaves: 1 << TREE_DEPTH_TEST,
+        }
+    );
+    for (i, leaf) in leaves.iter().enumerate() {
+        assert_eq!(mixer.get_commitment_by_index(&(i as u32)), Some(leaf.clone()));
+    }
+    assert_eq!(mixer.get_commitment_by_index(&5), None);
+
+    let all = mixer.get_commitments_range(&0, &5);
+    assert_eq!(all.iter().collect::<Vec<_>>(), leaves);
+    // Ranges are clamped to the deposited leaves.
+    let tail = mixer.get_commitments_range(&3, &100);
+    assert_eq!(tail.iter().collect::<Vec<_>>(), leaves[3..]);
+    assert!(mixer.get_commitments_range(&4, &2).is_empty());
+}
+
+/// Leaves from both deposit paths live in persistent storage, not in the
+/// size-capped instance entry, with their TTL extended.
+#[test]
+fn leaves_are_persistent_entries_with_an_extended_ttl() {
+    let env = Env::default();
+    env.cost_estimate().budget().reset_unlimited();
+    let _ = env.host().set_diagnostic_level(DiagnosticLevel::None);
+    let verifier_id = <Address as TestAddress>::generate(&env);
+    let mixer_id = register_mixer(&env, verifier_id);
+    let mixer = MixerContractClient::new(&env, &mixer_id);
+
+    mixer.deposit(&BytesN::from_array(&env, &be32_from_u64(300)));
+    let mut batch = SorobanVec::new(&env);
+    batch.push_back(BytesN::from_array(&env, &be32_from_u64(301)));
+    mixer.deposit_batch(&batch);
+
+    env.as_contract(&mixer_id, || {
+        for idx in 0u32..2 {
+            let key = (symbol_short!("leaf"), idx);
+            assert!(!env.storage().instance().has(&key));
+            assert_eq!(env.storage().persistent().get_ttl(&key), PERSISTENT_TTL_EXTEND_TO);
+        }
+    });
+}
+
+/// An anchor keeps the root of its ledger after later deposits move on.
+#[test]
+fn anchored_roots_survive_later_deposits() {
+    let env = Env::default();
+    env.cost_estimate().budget().reset_unlimited();
+    let _ = env.host().set_diagnostic_level(DiagnosticLevel::None);
+    let verifier_id = <Address as TestAddress>::generate(&env);
+    let mixer_id = register_mixer(&env, verifier_id);
+    let mixer = MixerContractClient::new(&env, &mixer_id);
+    assert_eq!(mixer.try_anchor_root(), Err(Ok(MixerError::RootNotSet)));
+
+    for i in 0u64..3 {
+        mixer.deposit(&BytesN::from_array(&env, &be32_from_u64(i + 200)));
+    }
+    env.ledger().set_sequence_number(42);
+    let root = mixer.get_root().unwrap();
+    assert_eq!(mixer.anchor_root(), (root.clone(), 42));
+
+    env.ledger().set_sequence_number(43);
+    mixer.deposit(&BytesN::from_array(&env, &be32_from_u64(203)));
+    assert_ne!(mixer.get_root(), Some(root.clone()));
+    assert_eq!(mixer.get_anchor(&42), Some(root));
+    assert_eq!(mixer.get_anchor(&43), None);
+
+    // Anchors accumulate, so they are persistent entries like leaves.
+    env.as_contract(&mixer_id, || {
+        let key = (symbol_short!("anchor"), 42u32);
+        assert!(!env.storage().instance().has(&key));
+        // Extended at ledger 42; one ledger has passed since.
+        assert_eq!(env.storage().persistent().get_ttl(&key), PERSISTENT_TTL_EXTEND_TO - 1);
+    });
+}
+
+#[test]
+fn deposit_batch_rejects_duplicates_and_empty_batches() {
+    let env = Env::default();
+    env.cost_estimate().budget().reset_unlimited();
+    let _ = env.host().set_diagnostic_level(DiagnosticLevel::None);
+    let verifier_id = <Address as TestAddress>::generate(&env);
+    let mixer_id: Address = register_mixer(&env, verifier_id);
+
+    let a = BytesN::from_array(&env, &[0x01; 32]);
+    let b = BytesN::from_array(&env, &[0x02; 32]);
+
+    let empty = SorobanVec::new(&env);
+    let err = env
+        .as_contract(&mixer_id, || MixerContract::deposit_batch(env.clone(), empty))
+        .err()
+        .expect("expected empty batch error");
+    assert_eq!(err as u32, MixerError::EmptyBatch as u32);
+
+    let within = SorobanVec::from_array(&env, [a.clone(), b.clone(), a.clone()]);
+    let err = env
+        .as_contract(&mixer_id, || MixerContract::deposit_batch(env.clone(), within))
+        .err()
+        .expect("expected duplicate within batch");
+    assert_eq!(err as u32, MixerError::CommitmentExists as u32);
+    // Nothing from the rejected batch was inserted.
+    assert_eq!(env.as_contract(&mixer_id, || MixerContract::get_root(env.clone())), None);
+
+    env.as_contract(&mixer_id, || MixerContract::deposit(env.clone(), b.clone()))
+        .unwrap();
+    let against_storage = SorobanVec::from_array(&env, [a, b]);
+    let err = env
+        .as_contract(&mixer_id, || MixerContract::deposit_batch(env.clone(), against_storage))
+        .err()
+        .expect("expected duplicate against storage");
+    assert_eq!(err as u32, MixerError::CommitmentExists as u32);
+}
+
+/// Before protocol 25 the host has no Poseidon2; deposits fail with an error instead of trapping.
+#[test]
+fn deposits_report_unsupported_protocol() {
+    let env = Env::default();
+    env.cost_estimate().budget().reset_unlimited();
+    let _ = env.host().set_diagnostic_level(DiagnosticLevel::None);
+    let verifier_id = <Address as TestAddress>::generate(&env);
+    let mixer_id: Address = register_mixer(&env, verifier_id);
+    env.ledger().set_protocol_version(24);
+
+    let cm = BytesN::from_array(&env, &[0x55; 32]);
+    let err = env
+        .as_contract(&mixer_id, || MixerContract::deposit(env.clone(), cm.clone()))
+        .err()
+        .expect("expected unsupported protocol");
+    assert_eq!(err as u32, MixerError::UnsupportedProtocol as u32);
+
+    let batch = SorobanVec::from_array(&env, [cm]);
+    let err = env
+        .as_contract(&mixer_id, || MixerContract::deposit_batch(env.clone(), batch))
+        .err()
+        .expect("expected unsupported protocol");
+    assert_eq!(err as u32, MixerError::UnsupportedProtocol as u32);
+    assert_eq!(env.as_contract(&mixer_id, || MixerContract::get_root(env.clone())), None);
+}
+
+/// Rotating the admin moves the right to call admin-gated functions.
+#[test]
+#[cfg(feature = "testutils")]
+fn transfer_admin_hands_over_set_root() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = <Address as TestAddress>::generate(&env);
+    let new_admin = <Address as TestAddress>::generate(&env);
+    let verifier_id = <Address as TestAddress>::generate(&env);
+    let mixer_id = env.register(MixerContract, (admin.clone(), verifier_id));
+    let mixer = MixerContractClient::new(&env, &mixer_id);
+    assert_eq!(mixer.admin(), Some(admin.clone()));
+
+    mixer.transfer_admin(&new_admin);
+    assert_eq!(env.auths()[0].0, admin);
+    assert_eq!(mixer.admin(), Some(new_admin.clone()));
+
+    let root = BytesN::from_array(&env, &[0xCD; 32]);
+    mixer.set_root(&root);
+    assert_eq!(env.auths()[0].0, new_admin);
+    assert_eq!(mixer.get_root(), Some(root));
+}
+
+/// After renouncing, admin-gated functions are disabled for everyone.
+#[test]
+#[cfg(feature = "testutils")]
+fn renounce_admin_disables_admin_calls() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = <Address as TestAddress>::generate(&env);
+    let verifier_id = <Address as TestAddress>::generate(&env);
+    let mixer_id = env.register(MixerContract, (admin.clone(), verifier_id));
+    let mixer = MixerContractClient::new(&env, &mixer_id);
+
+    mixer.renounce_admin();
+    assert_eq!(mixer.admin(), None);
+
+    let root = BytesN::from_array(&env, &[0xEF; 32]);
+    assert_eq!(mixer.try_set_root(&root), Err(Ok(MixerError::AdminNotConfigured)));
+    assert_eq!(
+        mixer.try_transfer_admin(&admin),
+        Err(Ok(MixerError::AdminNotConfigured))
+    );
+    assert_eq!(mixer.get_root(), None);
+}
+
+#[test]
+#[should_panic]
+fn transfer_admin_requires_admin_auth() {
+    let env = Env::default();
+    let verifier_id = <Address as TestAddress>::generate(&env);
+    let mixer_id = register_mixer(&env, verifier_id);
+    let mixer = MixerContractClient::new(&env, &mixer_id);
+    mixer.transfer_admin(&<Address as TestAddress>::generate(&env));
+}
+
+/// Stand-in deposit verifier: accepts a proof iff its first byte is 1 and
+/// the public input is exactly one 32-byte word.
+#[contract]
+struct StubDepositVerifier;
+
+#[contracterror]
+#[repr(u32)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum StubError {
+    Rejected = 1,
+}
+
+#[contractimpl]
+impl StubDepositVerifier {
+    pub fn verify_proof(
+        _env: Env,
+        public_inputs: Bytes,
+        proof_bytes: Bytes,
+    ) -> Result<(), StubError> {
+        if public_inputs.len() == 32 && proof_bytes.get(0) == Some(1) {
+            Ok(())
+        } else {
+            Err(StubError::Rejected)
+        }
+    }
+}
+
+/// `deposit_checked` only inserts a commitment the deposit verifier accepts.
+#[test]
+fn deposit_checked_requires_an_accepted_proof() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.cost_estimate().budget().reset_unlimited();
+    let _ = env.host().set_diagnostic_level(DiagnosticLevel::None);
+    let verifier_id = <Address as TestAddress>::generate(&env);
+    let mixer_id: Address = register_mixer(&env, verifier_id);
+    let mixer = MixerContractClient::new(&env, &mixer_id);
+
+    let cm = BytesN::from_array(&env, &[0x07; 32]);
+    let good = Bytes::from_slice(&env, &[1u8; PROOF_BYTES]);
+    let bad = Bytes::from_slice(&env, &[2u8; PROOF_BYTES]);
+    assert_eq!(
+        mixer.try_deposit_checked(&cm, &good),
+        Err(Ok(MixerError::DepositVerifierNotSet))
+    );
+
+    let stub_id = env.register(StubDepositVerifier, ());
+    mixer.set_deposit_verifier(&stub_id);
+    assert_eq!(
+        mixer.try_deposit_checked(&cm, &bad),
+        Err(Ok(MixerError::VerificationFailed))
+    );
+    assert_eq!(
+        mixer.try_deposit_checked(&cm, &Bytes::from_slice(&env, &[1u8; 32])),
+        Err(Ok(MixerError::VerificationFailed))
+    );
+    assert_eq!(mixer.get_root(), None);
+
+    assert_eq!(mixer.deposit_checked(&cm, &good), 0);
+    assert!(mixer.get_root().is_some());
+    assert_eq!(
+        mixer.try_deposit_checked(&cm, &good),
+        Err(Ok(MixerError::CommitmentExists))
+    );
+}
+
+/// Stand-in withdrawal verifier for a five-input circuit: accepts a proof
+/// iff its first byte is 1 and there are exactly five public inputs.
+#[contract]
+struct StubFiveInputVerifier;
+
+#[contractimpl]
+impl StubFiveInputVerifier {
+    pub fn verify_proof(
+        _env: Env,
+        public_inputs: Bytes,
+        proof_bytes: Bytes,
+    ) -> Result<(), StubError> {
+        if public_inputs.len() == 5 * 32 && proof_bytes.get(0) == Some(1) {
+            Ok(())
+        } else {
+            Err(StubError::Rejected)
+        }
+    }
+}
+
+/// `withdraw` reads the root and nullifier hash where the schema says.
+#[test]
+#[cfg(feature = "testutils")]
+fn withdraw_follows_a_five_input_schema() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.cost_estimate().budget().reset_unlimited();
+    let _ = env.host().set_diagnostic_level(DiagnosticLevel::None);
+    let stub_id = env.register(StubFiveInputVerifier, ());
+    let mixer_id: Address = register_mixer(&env, stub_id);
+    let mixer = MixerContractClient::new(&env, &mixer_id);
+
+    // [fee, nullifier_hash, recipient, root, chain_id]
+    let schema = PublicInputSchema {
+        root: 3,
+        nullifier_hash: 1,
+        recipient: None,
+    };
+    assert_eq!(
+        mixer.try_set_public_input_schema(&PublicInputSchema {
+            root: 2,
+            nullifier_hash: 2,
+            recipient: None,
+        }),
+        Err(Ok(MixerError::SchemaMismatch))
+    );
+    mixer.set_public_input_schema(&schema);
+    assert_eq!(mixer.public_input_schema(), schema);
+
+    let root = [0x44; 32];
+    let nf = [0x55; 32];
+    mixer.set_root(&BytesN::from_array(&env, &root));
+    let mut inputs = [0u8; 5 * 32];
+    inputs[32..64].copy_from_slice(&nf);
+    inputs[96..128].copy_from_slice(&root);
+    let proof = Bytes::from_slice(&env, &[1u8; PROOF_BYTES]);
+
+    // Three inputs do not reach the root at index 3.
+    let short = Bytes::from_slice(&env, &inputs[..3 * 32]);
+    assert_eq!(
+        mixer.try_withdraw(&short, &proof),
+        Err(Ok(MixerError::SchemaMismatch))
+    );
+
+    mixer.withdraw(&Bytes::from_slice(&env, &inputs), &proof);
+    assert!(mixer.is_nullifier_used(&BytesN::from_array(&env, &nf)));
+}
+
+/// Indexers decode events by the version topic; pin each payload's shape.
+#[test]
+#[cfg(feature = "testutils")]
+fn events_carry_the_version_and_structured_data() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.cost_estimate().budget().reset_unlimited();
+    let _ = env.host().set_diagnostic_level(DiagnosticLevel::None);
+    let stub_id = env.register(StubFiveInputVerifier, ());
+    let mixer_id: Address = register_mixer(&env, stub_id);
+    let mixer = MixerContractClient::new(&env, &mixer_id);
+    assert_eq!(EVENT_VERSION, 1);
+
+    let commitment = BytesN::from_array(&env, &[0x21; 32]);
+    mixer.deposit(&commitment);
+    let events = env.events().all().filter_by_contract(&mixer_id);
+    assert_eq!(
+        events.events(),
+        [DepositEvent {
+            version: &EVENT_VERSION,
+            idx: &0,
+            commitment: &commitment,
+        }
+        .to_xdr(&env, &mixer_id)]
+    );
+
+    mixer.set_public_input_schema(&PublicInputSchema {
+        root: 3,
+        nullifier_hash: 1,
+        recipient: None,
+    });
+    let root = mixer.get_root().unwrap();
+    let nf = [0x55; 32];
+    let mut inputs = [0u8; 5 * 32];
+    inputs[32..64].copy_from_slice(&nf);
+    inputs[96..128].copy_from_slice(&root.to_array());
+    mixer.withdraw(
+        &Bytes::from_slice(&env, &inputs),
+        &Bytes::from_slice(&env, &[1u8; PROOF_BYTES]),
+    );
+    let events = env.events().all().filter_by_contract(&mixer_id);
+    assert_eq!(
+        events.events(),
+        [WithdrawEvent {
+            version: &EVENT_VERSION,
+            nullifier_hash: &BytesN::from_array(&env, &nf),
+            root: &root,
+        }
+        .to_xdr(&env, &mixer_id)]
+    );
+}
+
+#[test]
+#[cfg(feature = "testutils")]
+fn withdraw_checks_the_recipient_allowlist() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.cost_estimate().budget().reset_unlimited();
+    let _ = env.host().set_diagnostic_level(DiagnosticLevel::None);
+    let stub_id = env.register(StubFiveInputVerifier, ());
+    let mixer_id: Address = register_mixer(&env, stub_id);
+    let mixer = MixerContractClient::new(&env, &mixer_id);
+
+    // [fee, nullifier_hash, recipient, root, chain_id]
+    let root = [0x44; 32];
+    mixer.set_root(&BytesN::from_array(&env, &root));
+    let proof = Bytes::from_slice(&env, &[1u8; PROOF_BYTES]);
+    let inputs = |nf: u8, recipient: u8| {
+        let mut inputs = [0u8; 5 * 32];
+        inputs[32..64].fill(nf);
+        inputs[64..96].fill(recipient);
+        inputs[96..128].copy_from_slice(&root);
+        Bytes::from_slice(&env, &inputs)
+    };
+    let alice = BytesN::from_array(&env, &[0xa1; 32]);
+    let bob = BytesN::from_array(&env, &[0xb0; 32]);
+
+    mixer.allow_recipient(&alice);
+    mixer.allow_recipient(&alice);
+    assert_eq!(mixer.allowed_recipient_count(), 1);
+    assert!(mixer.is_recipient_allowed(&alice));
+    assert!(!mixer.is_recipient_allowed(&bob));
+    env.as_contract(&mixer_id, || {
+        let key = (symbol_short!("allow"), alice.clone());
+        assert!(!env.storage().instance().has(&key));
+        assert_eq!(env.storage().persistent().get_ttl(&key), PERSISTENT_TTL_EXTEND_TO);
+    });
+
+    // Without a recipient index the allowlist cannot be checked.
+    let mut schema = PublicInputSchema {
+        root: 3,
+        nullifier_hash: 1,
+        recipient: None,
+    };
+    mixer.set_public_input_schema(&schema);
+    assert_eq!(
+        mixer.try_withdraw(&inputs(0x51, 0xa1), &proof),
+        Err(Ok(MixerError::RecipientNotAllowed))
+    );
+
+    schema.recipient = Some(3);
+    assert_eq!(
+        mixer.try_set_public_input_schema(&schema),
+        Err(Ok(MixerError::SchemaMismatch))
+    );
+    schema.recipient = Some(2);
+    mixer.set_public_input_schema(&schema);
+
+    assert_eq!(
+        mixer.try_withdraw(&inputs(0x52, 0xb0), &proof),
+        Err(Ok(MixerError::RecipientNotAllowed))
+    );
+    assert!(!mixer.is_nullifier_used(&BytesN::from_array(&env, &[0x52; 32])));
+
+    mixer.withdraw(&inputs(0x51, 0xa1), &proof);
+    assert!(mixer.is_nullifier_used(&BytesN::from_array(&env, &[0x51; 32])));
+
+    // Revoking the last entry turns the allowlist off again.
+    mixer.revoke_recipient(&alice);
+    assert_eq!(mixer.allowed_recipient_count(), 0);
+    mixer.withdraw(&inputs(0x52, 0xb0), &proof);
+}
+
+#[test]
+fn encrypted_notes_are_stored_beside_the_tree() {
+    let env = Env::default();
+    env.cost_estimate().budget().reset_unlimited();
+    let _ = env.host().set_diagnostic_level(DiagnosticLevel::None);
+    let verifier_id = <Address as TestAddress>::generate(&env);
+    let plain = MixerContractClient::new(&env, &register_mixer(&env, verifier_id.clone()));
+    let noted_id = register_mixer(&env, verifier_id);
+    let noted = MixerContractClient::new(&env, &noted_id);
+    let commitment = BytesN::from_array(&env, &be32_from_u64(7));
+    let note = Bytes::from_slice(&env, &[0x5a; 120]);
+
+    let too_large = Bytes::from_slice(&env, &[0u8; MAX_NOTE_BYTES as usize + 1]);
+    assert_eq!(
+        noted.try_deposit_with_note(&commitment, &too_large),
+        Err(Ok(MixerError::NoteTooLarge))
+    );
+    assert_eq!(noted.tree_info().next_index, 0);
+
+    assert_eq!(plain.deposit(&commitment), 0);
+    assert_eq!(noted.deposit_with_note(&commitment, &note), 0);
+    assert_eq!(noted.get_encrypted_note(&commitment), Some(note.clone()));
+    assert_eq!(plain.get_encrypted_note(&commitment), None);
+    // Kept alive as long as the leaf it recovers.
+    env.as_contract(&noted_id, || {
+        let key = (symbol_short!("note"), commitment.clone());
+        assert_eq!(env.storage().persistent().get_ttl(&key), PERSISTENT_TTL_EXTEND_TO);
+    });
+    // The note is opaque storage: the tree, and so every withdrawal proof
+    // against its root, is the same as for a plain deposit.
+    assert_eq!(noted.get_root(), plain.get_root());
+
+    assert_eq!(
+        noted.try_deposit_with_note(&commitment, &note),
+        Err(Ok(MixerError::CommitmentExists))
+    );
+}
+
+#[test]
+fn verify_only_returns_the_root_and_leaves_the_nullifier_unspent() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.cost_estimate().budget().reset_unlimited();
+    let _ = env.host().set_diagnostic_level(DiagnosticLevel::None);
+    let stub_id = env.register(StubFiveInputVerifier, ());
+    let mixer = MixerContractClient::new(&env, &register_mixer(&env, stub_id.clone()));
+
+    // [root, nullifier_hash, ...]; no stored root is needed.
+    let mut inputs = [0u8; 5 * 32];
+    inputs[..32].fill(0x44);
+    inputs[32..64].fill(0x51);
+    let inputs = Bytes::from_slice(&env, &inputs);
+    let proof = Bytes::from_slice(&env, &[1u8; PROOF_BYTES]);
+
+    let root = mixer.verify_only(&stub_id, &inputs, &proof);
+    assert_eq!(root, BytesN::from_array(&env, &[0x44; 32]));
+    assert!(!mixer.is_nullifier_used(&BytesN::from_array(&env, &[0x51; 32])));
+    assert_eq!(mixer.get_root(), None);
+
+    let rejected = Bytes::from_slice(&env, &[2u8; PROOF_BYTES]);
+    assert_eq!(
+        mixer.try_verify_only(&stub_id, &inputs, &rejected),
+        Err(Ok(MixerError::VerificationFailed))
+    );
+}
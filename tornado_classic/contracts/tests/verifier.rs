@@ -1,5 +1,5 @@
 use soroban_env_host::DiagnosticLevel;
-use soroban_sdk::{Address, Bytes, Env};
+use soroban_sdk::{testutils::Address as TestAddress, Address, Bytes, Env, Vec as SorobanVec};
 
 use std::sync::{Mutex, OnceLock};
 
@@ -25,7 +25,12 @@ fn verify_proof_with_constructor_vk() {
     assert_eq!(proof_bin.len(), PROOF_BYTES);
 
     let vk_bytes: Bytes = Bytes::from_slice(&env, vk_bin);
-    let verifier_id: Address = env.register(UltraHonkVerifierContract, (vk_bytes.clone(),));
+    let admin = <Address as TestAddress>::generate(&env);
+    let verifier_id: Address =
+        env.register(
+            UltraHonkVerifierContract,
+            (admin, vk_bytes.clone(), SorobanVec::<Bytes>::new(&env)),
+        );
     let proof_bytes: Bytes = Bytes::from_slice(&env, proof_bin);
     let public_inputs: Bytes = Bytes::from_slice(&env, pub_inputs_bin);
 
@@ -0,0 +1,47 @@
+//! Smoke binary for the soroban-free build. With `<vk> <proof>` it parses
+//! both and checks the round-0 sum of the sum-check; without arguments it
+//! prints the proof layout. It does not verify: that build has no transcript
+//! or pairing (see the crate docs).
+
+use std::{env, fs, process::ExitCode};
+use ultrahonk_soroban_verifier::{
+    field::Fr,
+    relations::SUBRELATION_NAMES,
+    sumcheck::check_sum,
+    utils::{load_proof_from_slice, load_vk_from_slice, proof_layout},
+    PROOF_BYTES,
+};
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let [vk_path, proof_path] = &args[..] else {
+        for (name, bytes) in proof_layout() {
+            println!("{name}: bytes {bytes:?}");
+        }
+        println!("{} subrelations", SUBRELATION_NAMES.len());
+        return ExitCode::SUCCESS;
+    };
+
+    let (Ok(vk_bytes), Ok(proof_bytes)) = (fs::read(vk_path), fs::read(proof_path)) else {
+        eprintln!("cannot read {vk_path} or {proof_path}");
+        return ExitCode::FAILURE;
+    };
+    let Some(vk) = load_vk_from_slice(&vk_bytes) else {
+        eprintln!("{vk_path} is not a bb v0.87 VK");
+        return ExitCode::FAILURE;
+    };
+    let Ok(proof) = load_proof_from_slice(&proof_bytes) else {
+        eprintln!(
+            "{proof_path} is {} bytes, not {PROOF_BYTES}",
+            proof_bytes.len()
+        );
+        return ExitCode::FAILURE;
+    };
+    println!(
+        "circuit_size {}, {} public inputs, round 0 sums to zero: {}",
+        vk.circuit_size,
+        vk.public_inputs_size,
+        check_sum(&proof.sumcheck_univariates[0], Fr::zero()),
+    );
+    ExitCode::SUCCESS
+}
@@ -1,8 +1,9 @@
-use crate::field::Fr;
+use crate::field::{hex_nibble, Fr};
+#[cfg(feature = "trace")]
 use crate::types::G1Point;
 
 #[cfg(not(feature = "std"))]
-use alloc::string::String;
+use alloc::{format, string::String, vec::Vec};
 
 /// trace! macro is a lightweight debug print macro that only outputs when the `trace` feature is enabled.
 /// you can use it like this: cargo test --features trace -- --nocapture / cargo run --features trace
@@ -16,123 +17,178 @@ macro_rules! trace {
     };
 }
 
+/// Lowercase hex of `bytes`, without a prefix.
+pub(crate) fn bytes_to_hex(bytes: &[u8]) -> String {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+    let mut s = String::with_capacity(2 * bytes.len());
+    for b in bytes {
+        s.push(DIGITS[(b >> 4) as usize] as char);
+        s.push(DIGITS[(b & 0xf) as usize] as char);
+    }
+    s
+}
+
 /// ark_bn254::Fr → BE fixed-width hex (0x + 64 nibbles)
 #[inline(always)]
 pub fn fr_to_hex(fr: &Fr) -> String {
     let mut s = String::from("0x");
-    s.push_str(&hex::encode(fr.to_bytes()));
+    s.push_str(&bytes_to_hex(&fr.to_bytes()));
     s
 }
 
 /// G1Point → (x_hex, y_hex)
+#[cfg(feature = "trace")]
 #[inline(always)]
 pub fn g1_to_hex(pt: &G1Point) -> (String, String) {
     let mut x = String::from("0x");
     let mut y = String::from("0x");
-    x.push_str(&hex::encode(pt.x));
-    y.push_str(&hex::encode(pt.y));
+    x.push_str(&bytes_to_hex(&pt.x));
+    y.push_str(&bytes_to_hex(&pt.y));
     (x, y)
 }
 
 /// Outputs commitment/scalar pairs
+#[cfg(feature = "trace")]
 pub fn dump_pairs(coms: &[G1Point], scalars: &[Fr], head_tail: usize) {
-    #[cfg(feature = "trace")]
-    {
-        assert_eq!(
-            coms.len(),
-            scalars.len(),
-            "commitment / scalar length mismatch"
-        );
+    assert_eq!(
+        coms.len(),
+        scalars.len(),
+        "commitment / scalar length mismatch"
+    );
 
-        let len = coms.len();
-        trace!("========= FULL LIST =========");
-        for i in 0..len {
-            if head_tail != usize::MAX && i >= head_tail && i < len - head_tail {
-                if i == head_tail {
-                    trace!("    ...");
-                }
-                continue;
+    let len = coms.len();
+    trace!("========= FULL LIST =========");
+    for i in 0..len {
+        if head_tail != usize::MAX && i >= head_tail && i < len - head_tail {
+            if i == head_tail {
+                trace!("    ...");
             }
-            let (x_hex, y_hex) = g1_to_hex(&coms[i]);
-            let s_hex = fr_to_hex(&scalars[i]);
-            trace!(
-                "[#{:02}]  s = {:>66}  C.x = {:>66}  C.y = {:>66}",
-                i,
-                s_hex,
-                x_hex,
-                y_hex
-            );
+            continue;
         }
-        trace!("================================");
-    }
-    #[cfg(not(feature = "trace"))]
-    {
-        let _ = (coms, scalars, head_tail);
+        let (x_hex, y_hex) = g1_to_hex(&coms[i]);
+        let s_hex = fr_to_hex(&scalars[i]);
+        trace!(
+            "[#{:02}]  s = {:>66}  C.x = {:>66}  C.y = {:>66}",
+            i,
+            s_hex,
+            x_hex,
+            y_hex
+        );
     }
+    trace!("================================");
 }
 
 /// Outputs a specific slice of commitment/scalar pairs, useful for
 /// cross-checking against Solidity's first 40 entities (1..=40).
-#[allow(dead_code)]
+#[cfg(feature = "trace")]
 pub fn dump_pairs_range(coms: &[G1Point], scalars: &[Fr], start: usize, end_inclusive: usize) {
-    #[cfg(feature = "trace")]
-    {
-        assert_eq!(
-            coms.len(),
-            scalars.len(),
-            "commitment / scalar length mismatch"
+    assert_eq!(
+        coms.len(),
+        scalars.len(),
+        "commitment / scalar length mismatch"
+    );
+    let end = end_inclusive.min(coms.len().saturating_sub(1));
+    let start = start.min(end);
+    trace!("========= RANGE LIST [{}..={}] =========", start, end);
+    for i in start..=end {
+        let (x_hex, y_hex) = g1_to_hex(&coms[i]);
+        let s_hex = fr_to_hex(&scalars[i]);
+        trace!(
+            "[#{:02}]  s = {}  C.x = {}  C.y = {}",
+            i,
+            s_hex,
+            x_hex,
+            y_hex
         );
-        let end = end_inclusive.min(coms.len().saturating_sub(1));
-        let start = start.min(end);
-        trace!("========= RANGE LIST [{}..={}] =========", start, end);
-        for i in start..=end {
-            let (x_hex, y_hex) = g1_to_hex(&coms[i]);
-            let s_hex = fr_to_hex(&scalars[i]);
-            trace!(
-                "[#{:02}]  s = {}  C.x = {}  C.y = {}",
-                i,
-                s_hex,
-                x_hex,
-                y_hex
-            );
-        }
-        trace!("========================================");
-    }
-    #[cfg(not(feature = "trace"))]
-    {
-        let _ = (coms, scalars, start, end_inclusive);
     }
+    trace!("========================================");
 }
 
 /// Debug Fr vector with hex output
+#[cfg(feature = "trace")]
 #[inline(always)]
 pub fn dbg_vec(tag: &str, xs: &[Fr]) {
-    #[cfg(feature = "trace")]
-    {
-        for (i, v) in xs.iter().enumerate() {
-            trace!(
-                "{tag}[{i:02}] = 0x{}",
-                hex::encode(v.to_bytes()),
-                tag = tag,
-                i = i
-            );
-        }
-    }
-    #[cfg(not(feature = "trace"))]
-    {
-        let _ = (tag, xs);
+    for (i, v) in xs.iter().enumerate() {
+        trace!(
+            "{tag}[{i:02}] = 0x{}",
+            bytes_to_hex(&v.to_bytes()),
+            tag = tag,
+            i = i
+        );
     }
 }
 
 /// Debug Fr with hex output
+#[cfg(feature = "trace")]
 #[inline(always)]
 pub fn dbg_fr(tag: &str, x: &Fr) {
-    #[cfg(feature = "trace")]
-    {
-        trace!("{:<18}: 0x{}", tag, hex::encode(x.to_bytes()));
+    trace!("{:<18}: 0x{}", tag, bytes_to_hex(&x.to_bytes()));
+}
+
+/// Name a derived challenge the way `trace` prints it and a reference lists
+/// it: `rho`, `beta`, … or `alpha_3`, `gate_challenge_0`, `sumcheck_u_5`.
+pub fn challenge_label(name: &str, index: usize) -> String {
+    match name {
+        "alpha" | "gate_challenge" | "sumcheck_u" => format!("{name}_{index}"),
+        _ => String::from(name),
+    }
+}
+
+/// Challenge values from a reference implementation, keyed by
+/// [`challenge_label`]. Challenges missing from the reference are not checked.
+#[derive(Clone, Debug, Default)]
+pub struct ReferenceTranscript {
+    entries: Vec<(String, Fr)>,
+}
+
+impl ReferenceTranscript {
+    pub fn new() -> Self {
+        Self::default()
     }
-    #[cfg(not(feature = "trace"))]
-    {
-        let _ = (tag, x);
+
+    pub fn insert(&mut self, name: &str, value: Fr) {
+        match self.entries.iter_mut().find(|(n, _)| n == name) {
+            Some(entry) => entry.1 = value,
+            None => self.entries.push((String::from(name), value)),
+        }
     }
+
+    pub fn get(&self, name: &str) -> Option<&Fr> {
+        self.entries.iter().find(|(n, _)| n == name).map(|(_, v)| v)
+    }
+
+    /// Parse `name = 0x<64 hex>` lines, the format `trace` prints the
+    /// transcript in. Lines without a 32-byte hex value are ignored.
+    pub fn parse(text: &str) -> Result<Self, &'static str> {
+        let mut out = Self::new();
+        for line in text.lines() {
+            let Some((name, value)) = line.split_once('=') else {
+                continue;
+            };
+            let Some(value) = value.trim().strip_prefix("0x") else {
+                continue;
+            };
+            if value.len() != 64 {
+                continue;
+            }
+            let mut bytes = [0u8; 32];
+            for (byte, pair) in bytes.iter_mut().zip(value.as_bytes().chunks(2)) {
+                let hi = hex_nibble(pair[0]).ok_or("invalid hex in reference")?;
+                let lo = hex_nibble(pair[1]).ok_or("invalid hex in reference")?;
+                *byte = (hi << 4) | lo;
+            }
+            out.insert(name.trim(), Fr::from_bytes(&bytes));
+        }
+        Ok(out)
+    }
+}
+
+/// First challenge at which the Rust transcript and a reference disagree.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DivergenceReport {
+    pub challenge_name: String,
+    pub rust_value: String,
+    pub reference_value: String,
+    /// The bytes hashed to derive the diverging challenge.
+    pub absorbed_bytes_hex: String,
 }
@@ -4,7 +4,8 @@ use soroban_sdk::{
     BytesN, Env, Vec,
 };
 
-const RHS_G2_BYTES: [u8; 128] = [
+/// `[1]₂`, the G2 generator, paired with `P0`.
+pub const RHS_G2_BYTES: [u8; 128] = [
     0x19, 0x8e, 0x93, 0x93, 0x92, 0x0d, 0x48, 0x3a, 0x72, 0x60, 0xbf, 0xb7, 0x31, 0xfb, 0x5d, 0x25,
     0xf1, 0xaa, 0x49, 0x33, 0x35, 0xa9, 0xe7, 0x12, 0x97, 0xe4, 0x85, 0xb7, 0xae, 0xf3, 0x12, 0xc2,
     0x18, 0x00, 0xde, 0xef, 0x12, 0x1f, 0x1e, 0x76, 0x42, 0x6a, 0x00, 0x66, 0x5e, 0x5c, 0x44, 0x79,
@@ -15,7 +16,8 @@ const RHS_G2_BYTES: [u8; 128] = [
     0xe3, 0xd1, 0xe7, 0x69, 0x0c, 0x43, 0xd3, 0x7b, 0x4c, 0xe6, 0xcc, 0x01, 0x66, 0xfa, 0x7d, 0xaa,
 ];
 
-const LHS_G2_BYTES: [u8; 128] = [
+/// `[x]₂` of the SRS, paired with `P1`.
+pub const LHS_G2_BYTES: [u8; 128] = [
     0x26, 0x0e, 0x01, 0xb2, 0x51, 0xf6, 0xf1, 0xc7, 0xe7, 0xff, 0x4e, 0x58, 0x07, 0x91, 0xde, 0xe8,
     0xea, 0x51, 0xd8, 0x7a, 0x35, 0x8e, 0x03, 0x8b, 0x4e, 0xfe, 0x30, 0xfa, 0xc0, 0x93, 0x83, 0xc1,
     0x01, 0x18, 0xc4, 0xd5, 0xb8, 0x37, 0xbc, 0xc2, 0xbc, 0x89, 0xb5, 0xb3, 0x98, 0xb5, 0x97, 0x4e,
@@ -26,6 +28,25 @@ const LHS_G2_BYTES: [u8; 128] = [
     0x11, 0xe6, 0xdd, 0x3f, 0x96, 0xe6, 0xce, 0xa2, 0x85, 0x4a, 0x87, 0xd4, 0xda, 0xcc, 0x5e, 0x55,
 ];
 
+/// First Soroban protocol version with the BN254 host functions. On older
+/// networks the calls trap, so [`crate::UltraHonkVerifier`] checks this first.
+pub const MIN_PROTOCOL_VERSION: u32 = 25;
+
+/// Whether the ledger's protocol provides the BN254 host functions.
+// `protocol_version` is deprecated, but nothing else tells us up front.
+#[allow(deprecated)]
+pub fn host_curve_available(env: &Env) -> bool {
+    env.ledger().protocol_version() >= MIN_PROTOCOL_VERSION
+}
+
+/// Name of the curve backend in effect, for diagnostics.
+///
+/// G1 arithmetic and pairings always go through the Soroban BN254 host
+/// functions; this crate has no pure-arkworks fallback.
+pub const fn active_backend_name() -> &'static str {
+    "soroban-precompile"
+}
+
 #[inline(always)]
 fn fr_to_bn254(env: &Env, fr: &Fr) -> Bn254Fr {
     Bn254Fr::from_bytes(BytesN::from_array(env, &fr.to_bytes()))
@@ -47,6 +68,10 @@ pub fn lhs_g2_affine(env: &Env) -> Bn254G2Affine {
 }
 
 /// Multi-scalar multiplication on G1: ∑ sᵢ·Cᵢ
+///
+/// An empty input, all-zero scalars, or terms that cancel yield the host
+/// identity (64 zero bytes). That encoding is accepted as-is by `g1_add` and
+/// `pairing_check`, so the result is never re-validated as an affine point.
 #[inline(always)]
 pub fn g1_msm(env: &Env, coms: &[G1Point], scalars: &[Fr]) -> Result<Bn254G1Affine, &'static str> {
     if coms.len() != scalars.len() {
@@ -66,15 +91,27 @@ pub fn g1_msm(env: &Env, coms: &[G1Point], scalars: &[Fr]) -> Result<Bn254G1Affi
     Ok(acc)
 }
 
+/// The `(G1, G2)` pairs whose pairing product must equal one.
+pub type PairingTerms = [(Bn254G1Affine, Bn254G2Affine); 2];
+
+/// Pair P0 with rhs_g2 and P1 with lhs_g2, in the order `pairing_check` uses.
+#[inline(always)]
+pub fn pairing_terms(env: &Env, p0: &Bn254G1Affine, p1: &Bn254G1Affine) -> PairingTerms {
+    [
+        (p0.clone(), rhs_g2_affine(env)),
+        (p1.clone(), lhs_g2_affine(env)),
+    ]
+}
+
 /// Pairing product check e(P0, rhs_g2) * e(P1, lhs_g2) == 1
 #[inline(always)]
 pub fn pairing_check(env: &Env, p0: &Bn254G1Affine, p1: &Bn254G1Affine) -> bool {
     let mut g1s: Vec<Bn254G1Affine> = Vec::new(env);
-    g1s.push_back(p0.clone());
-    g1s.push_back(p1.clone());
     let mut g2s: Vec<Bn254G2Affine> = Vec::new(env);
-    g2s.push_back(rhs_g2_affine(env));
-    g2s.push_back(lhs_g2_affine(env));
+    for (g1, g2) in pairing_terms(env, p0, p1) {
+        g1s.push_back(g1);
+        g2s.push_back(g2);
+    }
     env.crypto().bn254().pairing_check(g1s, g2s)
 }
 
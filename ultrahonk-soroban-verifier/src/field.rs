@@ -1,28 +1,70 @@
 use ark_bn254::Fr as ArkFr;
 use ark_ff::BigInteger256;
 use ark_ff::{Field, PrimeField, Zero};
-use core::ops::{Add, Mul, Neg, Sub};
-use hex;
+use core::iter::{Product, Sum};
+use core::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 
 #[cfg(not(feature = "std"))]
-use alloc::{borrow::ToOwned, string::String};
+use alloc::vec::Vec;
 
+/// Value of one ASCII hex digit. Decoded by hand so the verify path does
+/// not pull in the `hex` crate.
 #[inline(always)]
-fn normalize_hex(s: &str) -> String {
-    let raw = s.trim_start_matches("0x");
-    if raw.len() & 1 == 1 {
-        let mut out = String::with_capacity(raw.len() + 1);
-        out.push('0');
-        out.push_str(raw);
-        out
-    } else {
-        raw.to_owned()
+pub(crate) fn hex_nibble(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'a'..=b'f' => Some(c - b'a' + 10),
+        b'A'..=b'F' => Some(c - b'A' + 10),
+        _ => None,
+    }
+}
+
+/// The scalar-field operations the sum-check round logic needs, so it can
+/// be exercised over a small toy field with hand-checkable values. Only the
+/// sum-check rounds are generic; everything else uses [`Fr`] directly.
+pub trait ScalarField:
+    Copy + PartialEq + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+{
+    fn zero() -> Self;
+    fn one() -> Self;
+    fn from_u64(x: u64) -> Self;
+    fn inverse(&self) -> Option<Self>;
+
+    /// Barycentric weight `∏_{j≠i} (i - j)` of evaluation point `i` among
+    /// `0..BATCHED_RELATION_PARTIAL_LENGTH`.
+    fn barycentric_weight(i: usize) -> Self {
+        let mut w = Self::one();
+        for j in 0..crate::types::BATCHED_RELATION_PARTIAL_LENGTH {
+            if j != i {
+                w = w * (Self::from_u64(i as u64) - Self::from_u64(j as u64));
+            }
+        }
+        w
     }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct Fr(pub ArkFr);
 
+impl ScalarField for Fr {
+    fn zero() -> Self {
+        Fr::zero()
+    }
+    fn one() -> Self {
+        Fr::one()
+    }
+    fn from_u64(x: u64) -> Self {
+        Fr::from_u64(x)
+    }
+    fn inverse(&self) -> Option<Self> {
+        Fr::inverse(self)
+    }
+    /// Precomputed, rather than seven multiplications per point and round.
+    fn barycentric_weight(i: usize) -> Self {
+        Fr::from_bytes(&crate::sumcheck::BARY_BYTES[i])
+    }
+}
+
 impl Fr {
     /// Construct from u64.
     pub fn from_u64(x: u64) -> Self {
@@ -30,12 +72,16 @@ impl Fr {
     }
 
     /// Construct from hex string (with or without 0x prefix).
-    /// Normalize to even digits before `hex::decode` so OddLength exception won't occur.
+    /// Odd digit counts are read as if left-padded with a zero.
     pub fn from_str(s: &str) -> Self {
-        let bytes = hex::decode(normalize_hex(s)).expect("hex decode failed");
+        let raw = s.trim_start_matches("0x").as_bytes();
+        assert!(raw.len() <= 64, "hex decode failed");
         let mut padded = [0u8; 32];
-        let offset = 32 - bytes.len();
-        padded[offset..].copy_from_slice(&bytes);
+        // Fill from the least significant digit.
+        for (i, c) in raw.iter().rev().enumerate() {
+            let v = hex_nibble(*c).expect("hex decode failed");
+            padded[31 - i / 2] |= v << (4 * (i % 2));
+        }
         Self::from_bytes(&padded)
     }
 
@@ -47,6 +93,14 @@ impl Fr {
         Fr(ArkFr::from_le_bytes_mod_order(&tmp))
     }
 
+    /// Like [`from_bytes`](Self::from_bytes), but `None` if the big-endian
+    /// value is `>= r` instead of reducing it, so each scalar has exactly one
+    /// encoding.
+    pub fn from_bytes_canonical(bytes: &[u8; 32]) -> Option<Self> {
+        let fr = Self::from_bytes(bytes);
+        (fr.to_bytes() == *bytes).then_some(fr)
+    }
+
     /// Convert to 32-byte big-endian representation.
     #[inline(always)]
     pub fn to_bytes(&self) -> [u8; 32] {
@@ -62,6 +116,29 @@ impl Fr {
         self.0.inverse().map(Fr)
     }
 
+    /// Invert every element in place with a single field inversion
+    /// (Montgomery's trick: one inversion plus `3(n - 1)` multiplications).
+    ///
+    /// Returns `false` and leaves `values` untouched if any element is zero.
+    pub fn batch_inverse(values: &mut [Fr]) -> bool {
+        let mut prefix = Vec::with_capacity(values.len());
+        let mut acc = Fr::one();
+        for v in values.iter() {
+            prefix.push(acc);
+            acc *= *v;
+        }
+        let mut inv = match acc.inverse() {
+            Some(inv) => inv,
+            None => return false,
+        };
+        for (v, before) in values.iter_mut().zip(prefix).rev() {
+            let v_inv = inv * before;
+            inv *= *v;
+            *v = v_inv;
+        }
+        true
+    }
+
     pub fn zero() -> Self {
         Fr(ArkFr::zero())
     }
@@ -102,9 +179,51 @@ impl Mul for Fr {
     }
 }
 
+impl AddAssign for Fr {
+    fn add_assign(&mut self, rhs: Fr) {
+        self.0 += rhs.0;
+    }
+}
+
+impl SubAssign for Fr {
+    fn sub_assign(&mut self, rhs: Fr) {
+        self.0 -= rhs.0;
+    }
+}
+
+impl MulAssign for Fr {
+    fn mul_assign(&mut self, rhs: Fr) {
+        self.0 *= rhs.0;
+    }
+}
+
 impl Neg for Fr {
     type Output = Fr;
     fn neg(self) -> Fr {
         Fr(-self.0)
     }
 }
+
+impl Sum for Fr {
+    fn sum<I: Iterator<Item = Fr>>(iter: I) -> Fr {
+        iter.fold(Fr::zero(), Add::add)
+    }
+}
+
+impl<'a> Sum<&'a Fr> for Fr {
+    fn sum<I: Iterator<Item = &'a Fr>>(iter: I) -> Fr {
+        iter.copied().sum()
+    }
+}
+
+impl Product for Fr {
+    fn product<I: Iterator<Item = Fr>>(iter: I) -> Fr {
+        iter.fold(Fr::one(), Mul::mul)
+    }
+}
+
+impl<'a> Product<&'a Fr> for Fr {
+    fn product<I: Iterator<Item = &'a Fr>>(iter: I) -> Fr {
+        iter.copied().product()
+    }
+}
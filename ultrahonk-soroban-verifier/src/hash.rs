@@ -1,5 +1,11 @@
 use soroban_sdk::Bytes;
 
+/// Name of the Keccak backend in effect, for diagnostics. Always the Soroban
+/// host function; see [`crate::ec::active_backend_name`].
+pub const fn active_backend_name() -> &'static str {
+    "soroban-precompile"
+}
+
 /// Compute Keccak-256 using the Soroban host function.
 #[inline(always)]
 pub fn hash32(data: &Bytes) -> [u8; 32] {
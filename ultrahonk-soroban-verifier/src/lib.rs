@@ -1,20 +1,49 @@
+//! UltraHonk (bb v0.87) proof verifier for Soroban.
+//!
+//! Verifying needs the `soroban` feature (on by default): the transcript,
+//! MSM and pairing run on Soroban host functions, and there is no native
+//! Keccak or pairing backend. Without it the crate parses proofs and VKs and
+//! evaluates the relations and sum-check, but cannot verify a proof.
 #![cfg_attr(not(feature = "std"), no_std)]
 
 #[cfg(not(feature = "std"))]
 extern crate alloc;
 
 pub mod debug;
+#[cfg(feature = "soroban")]
 pub mod ec;
 pub mod field;
+#[cfg(feature = "soroban")]
 pub mod hash;
 pub mod relations;
+#[cfg(feature = "soroban")]
 pub mod shplemini;
 pub mod sumcheck;
+#[cfg(feature = "soroban")]
 pub mod transcript;
 pub mod types;
 pub mod utils;
+#[cfg(feature = "soroban")]
 pub mod verifier;
 pub const PROOF_FIELDS: usize = 456;
 pub const PROOF_BYTES: usize = PROOF_FIELDS * 32;
 
+// Pairing point object, 8 witness commitments, sum-check univariates,
+// entity evaluations, Gemini fold commitments and evaluations, then the
+// Shplonk and KZG quotients; G1 points take four limbs.
+const _: () = {
+    use types::*;
+    assert!(
+        PROOF_FIELDS
+            == PAIRING_POINTS_SIZE
+                + 8 * 4
+                + CONST_PROOF_SIZE_LOG_N * BATCHED_RELATION_PARTIAL_LENGTH
+                + NUMBER_OF_ENTITIES
+                + (CONST_PROOF_SIZE_LOG_N - 1) * 4
+                + CONST_PROOF_SIZE_LOG_N
+                + 2 * 4
+    );
+};
+
+#[cfg(feature = "soroban")]
 pub use verifier::UltraHonkVerifier;
@@ -154,7 +154,7 @@ fn accumulate_delta_range_relation(p: &[Fr], evals: &mut [Fr], domain_sep: Fr) {
     for i in 0..4 {
         let mut acc = deltas[i];
         for &n in &negs {
-            acc = acc * (deltas[i] + n);
+            acc *= deltas[i] + n;
         }
         evals[6 + i] = acc * wire(p, Wire::QRange) * domain_sep;
     }
@@ -231,7 +231,7 @@ fn accumulate_auxillary_relation(
         - wire(p, Wire::WoShift);
     non_native_field_gate_2 =
         non_native_field_gate_2 * limb_size() - wire(p, Wire::W4Shift) + limb_subproduct;
-    non_native_field_gate_2 = non_native_field_gate_2 * wire(p, Wire::Q4);
+    non_native_field_gate_2 *= wire(p, Wire::Q4);
 
     limb_subproduct =
         limb_subproduct * limb_size() + wire(p, Wire::WlShift) * wire(p, Wire::WrShift);
@@ -266,7 +266,7 @@ fn accumulate_auxillary_relation(
         + wire(p, Wire::Wl) * rp.eta
         + wire(p, Wire::Qc);
     let partial_record_check = memory_record_check;
-    memory_record_check = memory_record_check - wire(p, Wire::W4);
+    memory_record_check -= wire(p, Wire::W4);
 
     let index_delta = wire(p, Wire::WlShift) - wire(p, Wire::Wl);
     let record_delta = wire(p, Wire::W4Shift) - wire(p, Wire::W4);
@@ -365,37 +365,112 @@ fn accumulate_poseidon_internal_relation(p: &[Fr], evals: &mut [Fr], domain_sep:
     let u2_int = wire(p, Wire::Wr);
     let u3_int = wire(p, Wire::Wo);
     let u4_int = wire(p, Wire::W4);
-    let q_poseidon = wire(p, Wire::QPoseidon2Internal);
     let u_sum = u1_int + u2_int + u3_int + u4_int;
     let diag = internal_matrix_diagonal();
+    // Every lane is scaled by the same selector, so fold it once.
+    let scale = wire(p, Wire::QPoseidon2Internal) * domain_sep;
+
+    for (i, (u, shift)) in [
+        (u1_int, Wire::WlShift),
+        (u2_int, Wire::WrShift),
+        (u3_int, Wire::WoShift),
+        (u4_int, Wire::W4Shift),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        evals[22 + i] = (u * diag[i] + (u_sum - wire(p, shift))) * scale;
+    }
+}
 
-    let w1 = u1_int * diag[0] + u_sum;
-    let w2 = u2_int * diag[1] + u_sum;
-    let w3 = u3_int * diag[2] + u_sum;
-    let w4 = u4_int * diag[3] + u_sum;
-
-    evals[22] = (w1 - wire(p, Wire::WlShift)) * q_poseidon * domain_sep;
-    evals[23] = (w2 - wire(p, Wire::WrShift)) * q_poseidon * domain_sep;
-    evals[24] = (w3 - wire(p, Wire::WoShift)) * q_poseidon * domain_sep;
-    evals[25] = (w4 - wire(p, Wire::W4Shift)) * q_poseidon * domain_sep;
+/// Name of each subrelation, by output index.
+///
+/// Batching multiplies subrelation `i > 0` by `alphas[i - 1]`, so this order
+/// must match bb's relation tuple exactly: a mismatch only shows up as a
+/// failed sumcheck. Each name is prefixed with its relation family.
+pub const SUBRELATION_NAMES: [&str; NUMBER_OF_SUBRELATIONS] = [
+    "arithmetic_gate",
+    "arithmetic_mini_addition",
+    "permutation_grand_product",
+    "permutation_last_row",
+    "lookup_inverse",
+    "lookup_accumulate",
+    "delta_range_0",
+    "delta_range_1",
+    "delta_range_2",
+    "delta_range_3",
+    "elliptic_x",
+    "elliptic_y",
+    "auxiliary_identity",
+    "auxiliary_rom_adjacent_values",
+    "auxiliary_rom_index_monotonic",
+    "auxiliary_ram_adjacent_read_values",
+    "auxiliary_ram_index_monotonic",
+    "auxiliary_ram_access_type",
+    "poseidon2_external_0",
+    "poseidon2_external_1",
+    "poseidon2_external_2",
+    "poseidon2_external_3",
+    "poseidon2_internal_0",
+    "poseidon2_internal_1",
+    "poseidon2_internal_2",
+    "poseidon2_internal_3",
+];
+
+/// Relation families in batching order, each with the end (exclusive) of
+/// its run of subrelation indices; a family starts where the previous ends.
+pub const RELATION_GROUPS: [(&str, usize); 7] = [
+    ("arithmetic", 2),
+    ("permutation", 4),
+    ("lookup", 6),
+    ("delta_range", 10),
+    ("elliptic", 12),
+    ("auxiliary", 18),
+    ("poseidon2", NUMBER_OF_SUBRELATIONS),
+];
+
+/// Alpha-batched contribution of each family in [`RELATION_GROUPS`] to
+/// [`accumulate_relation_evaluations`]; the seven entries sum to it.
+pub fn grouped_contributions(
+    purported_evaluations: &[Fr],
+    rp: &RelationParameters,
+    alphas: &[Fr],
+    pow_partial_eval: Fr,
+) -> [Fr; 7] {
+    let evaluations = evaluate_subrelations(purported_evaluations, rp, pow_partial_eval);
+    let mut groups = [Fr::zero(); 7];
+    let mut start = 0;
+    for (group, &(_, end)) in groups.iter_mut().zip(RELATION_GROUPS.iter()) {
+        for i in start..end {
+            let weighted = if i == 0 {
+                evaluations[0]
+            } else {
+                evaluations[i] * alphas[i - 1]
+            };
+            *group += weighted;
+        }
+        start = end;
+    }
+    groups
 }
 
 /// Batch all NUM_SUBRELATIONS = 26 subrelations with the alpha challenges.
 fn scale_and_batch_subrelations(evaluations: &[Fr], subrelation_challenges: &[Fr]) -> Fr {
-    let mut accumulator = evaluations[0];
-    for i in 1..NUMBER_OF_SUBRELATIONS {
-        accumulator = accumulator + evaluations[i] * subrelation_challenges[i - 1];
-    }
-    accumulator
+    evaluations[0]
+        + evaluations[1..NUMBER_OF_SUBRELATIONS]
+            .iter()
+            .zip(subrelation_challenges)
+            .map(|(&e, &alpha)| e * alpha)
+            .sum::<Fr>()
 }
 
-/// Main entrypoint: accumulate all subrelations and batch with alphas.
-pub fn accumulate_relation_evaluations(
+/// Evaluate every subrelation at `purported_evaluations`, each scaled by
+/// `pow_partial_eval`, without batching. Index `i` is subrelation `i`.
+pub fn evaluate_subrelations(
     purported_evaluations: &[Fr],
     rp: &RelationParameters,
-    alphas: &[Fr],
     pow_partial_eval: Fr,
-) -> Fr {
+) -> [Fr; NUMBER_OF_SUBRELATIONS] {
     let mut evaluations = [Fr::zero(); NUMBER_OF_SUBRELATIONS];
 
     accumulate_arithmetic_relation(purported_evaluations, &mut evaluations, pow_partial_eval);
@@ -430,6 +505,57 @@ pub fn accumulate_relation_evaluations(
         pow_partial_eval,
     );
 
-    let accumulator = scale_and_batch_subrelations(&evaluations, alphas);
-    accumulator
+    evaluations
+}
+
+/// Evaluate only the permutation subrelations, `(grand product, last row)`,
+/// i.e. entries 2 and 3 of [`evaluate_subrelations`].
+///
+/// Like every subrelation they only vanish on individual hypercube rows; at
+/// the sum-check challenge point of a valid proof they generally do not.
+pub fn check_permutation_only(
+    purported_evaluations: &[Fr],
+    rp: &RelationParameters,
+    pow_partial_eval: Fr,
+) -> (Fr, Fr) {
+    let mut evaluations = [Fr::zero(); NUMBER_OF_SUBRELATIONS];
+    accumulate_permutation_relation(
+        purported_evaluations,
+        rp,
+        &mut evaluations,
+        pow_partial_eval,
+    );
+    (evaluations[2], evaluations[3])
+}
+
+/// Evaluate only the lookup subrelations, `(inverse, accumulate)`, i.e.
+/// entries 4 and 5 of [`evaluate_subrelations`].
+///
+/// As with [`check_permutation_only`], these vanish on single hypercube rows
+/// (the accumulate one only summed over all rows), not at the sum-check
+/// challenge point of a valid proof.
+pub fn check_lookup_only(
+    purported_evaluations: &[Fr],
+    rp: &RelationParameters,
+    pow_partial_eval: Fr,
+) -> (Fr, Fr) {
+    let mut evaluations = [Fr::zero(); NUMBER_OF_SUBRELATIONS];
+    accumulate_log_derivative_lookup_relation(
+        purported_evaluations,
+        rp,
+        &mut evaluations,
+        pow_partial_eval,
+    );
+    (evaluations[4], evaluations[5])
+}
+
+/// Main entrypoint: accumulate all subrelations and batch with alphas.
+pub fn accumulate_relation_evaluations(
+    purported_evaluations: &[Fr],
+    rp: &RelationParameters,
+    alphas: &[Fr],
+    pow_partial_eval: Fr,
+) -> Fr {
+    let evaluations = evaluate_subrelations(purported_evaluations, rp, pow_partial_eval);
+    scale_and_batch_subrelations(&evaluations, alphas)
 }
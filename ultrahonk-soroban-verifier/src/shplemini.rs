@@ -4,22 +4,134 @@ use crate::ec::{g1_msm, pairing_check};
 use crate::field::Fr;
 use crate::trace;
 use crate::types::{
-    G1Point, Proof, Transcript, VerificationKey, CONST_PROOF_SIZE_LOG_N, NUMBER_OF_ENTITIES,
-    NUMBER_TO_BE_SHIFTED, NUMBER_UNSHIFTED,
+    G1Point, GeminiR, Proof, Rho, ShplonkNu, ShplonkZ, Transcript, VerificationKey,
+    CONST_PROOF_SIZE_LOG_N, NUMBER_OF_ENTITIES, NUMBER_TO_BE_SHIFTED, NUMBER_UNSHIFTED,
 };
-use soroban_sdk::Env;
+use soroban_sdk::{crypto::bn254::Bn254G1Affine, Env};
+
+/// Message for each fold commitment index, for [`ShpleminiError::message`].
+const FOLD_COMM_OFF_CURVE: [&str; CONST_PROOF_SIZE_LOG_N - 1] = [
+    "gemini_fold_comms[0] is not on the curve",
+    "gemini_fold_comms[1] is not on the curve",
+    "gemini_fold_comms[2] is not on the curve",
+    "gemini_fold_comms[3] is not on the curve",
+    "gemini_fold_comms[4] is not on the curve",
+    "gemini_fold_comms[5] is not on the curve",
+    "gemini_fold_comms[6] is not on the curve",
+    "gemini_fold_comms[7] is not on the curve",
+    "gemini_fold_comms[8] is not on the curve",
+    "gemini_fold_comms[9] is not on the curve",
+    "gemini_fold_comms[10] is not on the curve",
+    "gemini_fold_comms[11] is not on the curve",
+    "gemini_fold_comms[12] is not on the curve",
+    "gemini_fold_comms[13] is not on the curve",
+    "gemini_fold_comms[14] is not on the curve",
+    "gemini_fold_comms[15] is not on the curve",
+    "gemini_fold_comms[16] is not on the curve",
+    "gemini_fold_comms[17] is not on the curve",
+    "gemini_fold_comms[18] is not on the curve",
+    "gemini_fold_comms[19] is not on the curve",
+    "gemini_fold_comms[20] is not on the curve",
+    "gemini_fold_comms[21] is not on the curve",
+    "gemini_fold_comms[22] is not on the curve",
+    "gemini_fold_comms[23] is not on the curve",
+    "gemini_fold_comms[24] is not on the curve",
+    "gemini_fold_comms[25] is not on the curve",
+    "gemini_fold_comms[26] is not on the curve",
+];
+
+/// Why the batched opening was rejected. Only `PairingFailed` means a
+/// well-formed proof that does not open; the others point at a malformed
+/// proof or degenerate challenges.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShpleminiError {
+    /// Proof dimensions do not fit `vk.log_circuit_size`.
+    LayoutMismatch,
+    /// `shplonk_q` is the identity.
+    ShplonkQIdentity,
+    /// `kzg_quotient` is the identity.
+    KzgQuotientIdentity,
+    /// `gemini_fold_comms[index]` is used and not on the curve.
+    InvalidCommitment { index: usize },
+    /// A denominator built from `r^{2^round}` is zero: `z ∓ r^{2^round}`, the
+    /// fold denominator of that round, or `r` itself for round 0.
+    DenominatorZero { round: usize },
+    /// The MSM cancelled to the identity.
+    IdentityP0,
+    /// The final pairing check did not hold.
+    PairingFailed,
+}
+
+impl ShpleminiError {
+    /// Human-readable detail, as the `&'static str` errors elsewhere.
+    pub fn message(&self) -> &'static str {
+        match self {
+            ShpleminiError::LayoutMismatch => "proof layout does not match log_n",
+            ShpleminiError::ShplonkQIdentity => "shplonk_q is the identity",
+            ShpleminiError::KzgQuotientIdentity => "kzg_quotient is the identity",
+            ShpleminiError::InvalidCommitment { index } => FOLD_COMM_OFF_CURVE
+                .get(*index)
+                .copied()
+                .unwrap_or("fold commitment is not on the curve"),
+            ShpleminiError::DenominatorZero { .. } => "shplemini denominator is zero",
+            ShpleminiError::IdentityP0 => "shplemini P0 is the identity",
+            ShpleminiError::PairingFailed => "Shplonk pairing check failed",
+        }
+    }
+}
 
 /// Shplemini verification
+///
+/// `proof` must be the one `verify_sumcheck` accepted: sum-check only checks
+/// that `sumcheck_evaluations` satisfy the relation, and this opening is what
+/// ties those same values to the commitments.
 pub fn verify_shplemini(
     env: &Env,
     proof: &Proof,
     vk: &VerificationKey,
     tp: &Transcript,
-) -> Result<(), &'static str> {
+    generator: &G1Point,
+) -> Result<(), ShpleminiError> {
+    let (p0, p1) = shplemini_pairing_points(env, proof, vk, tp, generator)?;
+    if pairing_check(env, &p0, &p1) {
+        Ok(())
+    } else {
+        Err(ShpleminiError::PairingFailed)
+    }
+}
+
+/// Reduce the batched opening to the two G1 inputs of the final pairing:
+/// `P0` (the MSM) and `P1 = -kzg_quotient`, already negated.
+///
+/// Fails with `LayoutMismatch` unless [`Proof::validate_dimensions`] passes
+/// for `vk.log_circuit_size`.
+pub fn shplemini_pairing_points(
+    env: &Env,
+    proof: &Proof,
+    vk: &VerificationKey,
+    tp: &Transcript,
+    generator: &G1Point,
+) -> Result<(Bn254G1Affine, Bn254G1Affine), ShpleminiError> {
+    proof
+        .validate_dimensions(vk.log_circuit_size)
+        .map_err(|_| ShpleminiError::LayoutMismatch)?;
+    // Both must be genuine commitments; the identity would let the quotient
+    // terms drop out of the pairing.
+    if proof.shplonk_q == G1Point::infinity() {
+        return Err(ShpleminiError::ShplonkQIdentity);
+    }
+    if proof.kzg_quotient == G1Point::infinity() {
+        return Err(ShpleminiError::KzgQuotientIdentity);
+    }
+    let Rho(rho) = tp.rho;
+    let GeminiR(gemini_r) = tp.gemini_r;
+    let ShplonkNu(shplonk_nu) = tp.shplonk_nu;
+    let ShplonkZ(shplonk_z) = tp.shplonk_z;
+
     // 1) r^{2^i}
     let log_n = vk.log_circuit_size as usize;
     let mut r_pows = [Fr::zero(); CONST_PROOF_SIZE_LOG_N];
-    r_pows[0] = tp.gemini_r;
+    r_pows[0] = gemini_r;
     for i in 1..log_n {
         r_pows[i] = r_pows[i - 1] * r_pows[i - 1];
     }
@@ -29,23 +141,43 @@ pub fn verify_shplemini(
     //   [0]                 = shplonk_Q
     //   [1..=40]            = VK + proof entities (NUMBER_OF_ENTITIES)
     //   [41..=67]           = gemini_fold_comms (CONST_PROOF_SIZE_LOG_N - 1 = 27)
-    //   [68]                = generator (1,2) with const_acc scalar
+    //   [68]                = SRS generator with const_acc scalar
     //   [69]                = kzg_quotient with scalar z
     const TOTAL: usize = 1 + NUMBER_OF_ENTITIES + CONST_PROOF_SIZE_LOG_N + 1;
     trace!("total = {}", TOTAL);
     let mut scalars = [Fr::zero(); TOTAL];
     let mut coms = [G1Point::infinity(); TOTAL];
 
-    // 3) compute shplonk weights
-    let pos0 = (tp.shplonk_z - r_pows[0])
-        .inverse()
-        .ok_or("shplonk denominator (z - r^0) is zero")?;
-    let neg0 = (tp.shplonk_z + r_pows[0])
-        .inverse()
-        .ok_or("shplonk denominator (z + r^0) is zero")?;
-    let unshifted = pos0 + tp.shplonk_nu * neg0;
-    let gemini_r_inv = tp.gemini_r.inverse().ok_or("gemini_r challenge is zero")?;
-    let shifted = gemini_r_inv * (pos0 - tp.shplonk_nu * neg0);
+    // 3) invert every denominator in one pass: per round j < log_n,
+    //    z - r^{2^j}, z + r^{2^j} and the fold denominator
+    //    r^{2^j}(1 - u_j) + u_j, then r itself. Unused slots stay at one.
+    const C: usize = CONST_PROOF_SIZE_LOG_N;
+    let mut inv = [Fr::one(); 3 * C + 1];
+    if gemini_r == Fr::zero() {
+        return Err(ShpleminiError::DenominatorZero { round: 0 });
+    }
+    inv[3 * C] = gemini_r;
+    for j in 0..log_n {
+        let u = tp.sumcheck_u_challenges[j];
+        inv[j] = shplonk_z - r_pows[j];
+        inv[C + j] = shplonk_z + r_pows[j];
+        inv[2 * C + j] = r_pows[j] * (Fr::one() - u) + u;
+        if [inv[j], inv[C + j], inv[2 * C + j]].contains(&Fr::zero()) {
+            return Err(ShpleminiError::DenominatorZero { round: j });
+        }
+    }
+    let inverted = Fr::batch_inverse(&mut inv);
+    debug_assert!(inverted, "zero denominators are rejected above");
+    let (pos_inv, rest) = inv.split_at(C);
+    let (neg_inv, rest) = rest.split_at(C);
+    let (fold_inv, gemini_r_inv) = rest.split_at(C);
+    let gemini_r_inv = gemini_r_inv[0];
+
+    // shplonk weights
+    let pos0 = pos_inv[0];
+    let neg0 = neg_inv[0];
+    let unshifted = pos0 + shplonk_nu * neg0;
+    let shifted = gemini_r_inv * (pos0 - shplonk_nu * neg0);
     // 4) shplonk_Q
     scalars[0] = Fr::one();
     coms[0] = proof.shplonk_q.clone();
@@ -67,8 +199,8 @@ pub fn verify_shplemini(
             -shifted
         } * rho_pow;
         scalars[1 + idx] = scalar;
-        eval_acc = eval_acc + (*eval * rho_pow);
-        rho_pow = rho_pow * tp.rho;
+        eval_acc += *eval * rho_pow;
+        rho_pow *= rho;
     }
     // 6) load VK & proof
     {
@@ -147,59 +279,60 @@ pub fn verify_shplemini(
         let u = tp.sumcheck_u_challenges[j - 1];
         let num = r2 * cur * Fr::from_u64(2)
             - proof.gemini_a_evaluations[j - 1] * (r2 * (Fr::one() - u) - u);
-        let den = r2 * (Fr::one() - u) + u;
-        let den_inv = den.inverse().ok_or("fold round denominator is zero")?;
-        cur = num * den_inv;
+        cur = num * fold_inv[j - 1];
         fold_pos[j - 1] = cur;
     }
     // 8) accumulate constant term
-    let mut const_acc = fold_pos[0] * pos0 + proof.gemini_a_evaluations[0] * tp.shplonk_nu * neg0;
-    let mut v_pow = tp.shplonk_nu * tp.shplonk_nu;
+    let mut const_acc = fold_pos[0] * pos0 + proof.gemini_a_evaluations[0] * shplonk_nu * neg0;
+    let nu_sq = shplonk_nu * shplonk_nu;
+    let mut v_pow = nu_sq;
     // 9) further folding + commit
     // Base index where fold commitments start
     let base = 1 + NUMBER_OF_ENTITIES;
+    // Check the fold commitments that get a nonzero scalar up front, so a bad
+    // one is named instead of trapping inside the MSM.
+    for (j, com) in proof.gemini_fold_comms[..log_n - 1].iter().enumerate() {
+        if !com.is_on_curve() {
+            return Err(ShpleminiError::InvalidCommitment { index: j });
+        }
+    }
     for j in 1..log_n {
-        let pos_inv = (tp.shplonk_z - r_pows[j])
-            .inverse()
-            .ok_or("shplonk denominator (z - r^i) is zero")?;
-        let neg_inv = (tp.shplonk_z + r_pows[j])
-            .inverse()
-            .ok_or("shplonk denominator (z + r^i) is zero")?;
-        let sp = v_pow * pos_inv;
-        let sn = v_pow * tp.shplonk_nu * neg_inv;
+        let sp = v_pow * pos_inv[j];
+        let sn = v_pow * shplonk_nu * neg_inv[j];
 
         scalars[base + j - 1] = -(sp + sn);
-        const_acc = const_acc + proof.gemini_a_evaluations[j] * sn + fold_pos[j] * sp;
+        const_acc += proof.gemini_a_evaluations[j] * sn + fold_pos[j] * sp;
 
-        v_pow = v_pow * tp.shplonk_nu * tp.shplonk_nu;
+        v_pow *= nu_sq;
 
         coms[base + j - 1] = proof.gemini_fold_comms[j - 1].clone();
     }
 
-    // Fill remaining (dummy) fold commitments so MSM layout matches Solidity (total 27 entries)
-    for i in (log_n - 1)..(CONST_PROOF_SIZE_LOG_N - 1) {
-        coms[base + i] = proof.gemini_fold_comms[i].clone();
-    }
+    // The proof always carries CONST_PROOF_SIZE_LOG_N - 1 = 27 fold
+    // commitments; entries from log_n - 1 on are padding. Their MSM slots keep
+    // the identity and a zero scalar, which `g1_msm` skips, so the padding is
+    // neither read nor multiplied. Slot positions still match Solidity.
 
     // 10) add generator
     // Generator goes right after all fold commitments (27 entries)
     let one_idx = base + (CONST_PROOF_SIZE_LOG_N - 1);
     trace!("one_idx = {}", one_idx);
-    coms[one_idx] = G1Point::generator();
+    coms[one_idx] = *generator;
     scalars[one_idx] = const_acc;
 
     // 11) add quotient
     let q_idx = one_idx + 1;
     trace!("q_idx = {}", q_idx);
     coms[q_idx] = proof.kzg_quotient.clone();
-    scalars[q_idx] = tp.shplonk_z;
+    scalars[q_idx] = shplonk_z;
 
-    // 12) MSM + pairing
-    let p0 = g1_msm(env, &coms, &scalars)?;
-    let p1 = negate(env, &proof.kzg_quotient);
-    if pairing_check(env, &p0, &p1) {
-        Ok(())
-    } else {
-        Err("Shplonk pairing check failed")
+    // 12) MSM; the pairing itself is left to the caller
+    let p0 = g1_msm(env, &coms, &scalars).map_err(|_| ShpleminiError::LayoutMismatch)?;
+    // e(O, ·) == 1, so a cancelled MSM would leave only e(P1, x·G2); no honest
+    // proof gets here, and the pairing result should not decide it.
+    if p0.to_array() == G1Point::infinity().to_bytes() {
+        return Err(ShpleminiError::IdentityP0);
     }
+    let p1 = negate(env, &proof.kzg_quotient);
+    Ok((p0, p1))
 }
@@ -1,11 +1,12 @@
 //! Sum-check verifier
 use crate::{
-    field::Fr,
+    field::{Fr, ScalarField},
     relations::accumulate_relation_evaluations,
     types::{Transcript, VerificationKey, BATCHED_RELATION_PARTIAL_LENGTH},
 };
 
-const BARY_BYTES: [[u8; 32]; BATCHED_RELATION_PARTIAL_LENGTH] = [
+/// `∏_{j≠i} (i - j)` for `i` in `0..8`, as big-endian BN254 scalars.
+pub(crate) const BARY_BYTES: [[u8; 32]; BATCHED_RELATION_PARTIAL_LENGTH] = [
     [
         0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29, 0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81, 0x58,
         0x5d, 0x28, 0x33, 0xe8, 0x48, 0x79, 0xb9, 0x70, 0x91, 0x43, 0xe1, 0xf5, 0x93, 0xef, 0xff,
@@ -50,29 +51,30 @@ const BARY_BYTES: [[u8; 32]; BATCHED_RELATION_PARTIAL_LENGTH] = [
 
 /// Check if the sum of two univariates equals the target value
 #[inline(always)]
-fn check_sum(round_univariate: &[Fr], round_target: Fr) -> bool {
+pub fn check_sum<F: ScalarField>(round_univariate: &[F], round_target: F) -> bool {
     let total_sum = round_univariate[0] + round_univariate[1];
     total_sum == round_target
 }
 
-/// Calculate next target value for the sum-check
+/// Calculate next target value for the sum-check: the round univariate,
+/// given by its values at `0..8`, evaluated at `round_challenge`.
 #[inline(always)]
-fn compute_next_target_sum(
-    round_univariate: &[Fr],
-    round_challenge: Fr,
-) -> Result<Fr, &'static str> {
+pub fn compute_next_target_sum<F: ScalarField>(
+    round_univariate: &[F],
+    round_challenge: F,
+) -> Result<F, &'static str> {
     // B(χ) = ∏ (χ - i)
-    let mut b_poly = Fr::one();
+    let mut b_poly = F::one();
     for i in 0..BATCHED_RELATION_PARTIAL_LENGTH {
-        b_poly = b_poly * (round_challenge - Fr::from_u64(i as u64));
+        b_poly = b_poly * (round_challenge - F::from_u64(i as u64));
     }
 
     // Σ u_i / (BARY[i] * (χ - i))
-    let mut acc = Fr::zero();
+    let mut acc = F::zero();
     for i in 0..BATCHED_RELATION_PARTIAL_LENGTH {
-        let bary_val = Fr::from_bytes(&BARY_BYTES[i]);
+        let bary_val = F::barycentric_weight(i);
 
-        let denom = bary_val * (round_challenge - Fr::from_u64(i as u64));
+        let denom = bary_val * (round_challenge - F::from_u64(i as u64));
         let inv = denom.inverse().ok_or("denom zero")?;
         acc = acc + (round_univariate[i] * inv);
     }
@@ -80,20 +82,38 @@ fn compute_next_target_sum(
     Ok(b_poly * acc)
 }
 
+/// Fold one round's gate challenge into the pow polynomial evaluation.
 #[inline(always)]
-fn partially_evaluate_pow(
-    gate_challenge: Fr,
-    pow_partial_evaluation: Fr,
-    round_challenge: Fr,
-) -> Fr {
-    pow_partial_evaluation * (Fr::one() + round_challenge * (gate_challenge - Fr::one()))
+pub fn partially_evaluate_pow<F: ScalarField>(
+    gate_challenge: F,
+    pow_partial_evaluation: F,
+    round_challenge: F,
+) -> F {
+    pow_partial_evaluation * (F::one() + round_challenge * (gate_challenge - F::one()))
 }
 
+/// Expects [`Proof::validate_dimensions`](crate::types::Proof::validate_dimensions)
+/// to have passed for `vk.log_circuit_size`.
+///
+/// The claimed sum starts at zero, so round 0 requires `u[0] + u[1] == 0`
+/// whatever the challenges are. A proof failing that is malformed rather
+/// than built against a diverging transcript, and gets its own error.
 pub fn verify_sumcheck(
     proof: &crate::types::Proof,
     tp: &Transcript,
     vk: &VerificationKey,
 ) -> Result<(), &'static str> {
+    let (grand_honk_relation_sum, round_target) = sumcheck_final_claim(proof, tp, vk)?;
+    check_final_claim(grand_honk_relation_sum, round_target)
+}
+
+/// Run every round check of [`verify_sumcheck`] and return the final
+/// `(grand_relation, target)` pair it compares, without comparing them.
+pub fn sumcheck_final_claim(
+    proof: &crate::types::Proof,
+    tp: &Transcript,
+    vk: &VerificationKey,
+) -> Result<(Fr, Fr), &'static str> {
     let log_n = vk.log_circuit_size as usize;
     let mut round_target = Fr::zero();
     let mut pow_partial_evaluation = Fr::one();
@@ -103,6 +123,9 @@ pub fn verify_sumcheck(
         let round_univariate = &proof.sumcheck_univariates[round];
 
         if !check_sum(round_univariate, round_target) {
+            if round == 0 {
+                return Err("round 0 target must be zero; prover sent nonzero u[0]+u[1]");
+            }
             return Err("round failed");
         }
 
@@ -122,19 +145,30 @@ pub fn verify_sumcheck(
         &tp.alphas,
         pow_partial_evaluation,
     );
+    Ok((grand_honk_relation_sum, round_target))
+}
 
+/// The last step of [`verify_sumcheck`]: the grand relation must equal the
+/// target the rounds left behind.
+pub fn check_final_claim(
+    grand_honk_relation_sum: Fr,
+    round_target: Fr,
+) -> Result<(), &'static str> {
     if grand_honk_relation_sum == round_target {
         Ok(())
     } else {
         crate::trace!("===== SUMCHECK FINAL CHECK FAILED =====");
         crate::trace!(
             "grand_relation = 0x{}",
-            hex::encode(grand_honk_relation_sum.to_bytes())
+            crate::debug::bytes_to_hex(&grand_honk_relation_sum.to_bytes())
+        );
+        crate::trace!(
+            "target = 0x{}",
+            crate::debug::bytes_to_hex(&round_target.to_bytes())
         );
-        crate::trace!("target = 0x{}", hex::encode(round_target.to_bytes()));
         crate::trace!(
             "difference = 0x{}",
-            hex::encode((grand_honk_relation_sum - round_target).to_bytes())
+            crate::debug::bytes_to_hex(&(grand_honk_relation_sum - round_target).to_bytes())
         );
         crate::trace!("======================================");
         Err("sumcheck final mismatch")
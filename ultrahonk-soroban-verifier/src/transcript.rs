@@ -5,7 +5,8 @@ use crate::{
     field::Fr,
     hash::hash32,
     types::{
-        G1Point, Proof, RelationParameters, Transcript, CONST_PROOF_SIZE_LOG_N, NUMBER_OF_ALPHAS,
+        G1Point, GeminiR, Proof, RelationParameters, Rho, ShplonkNu, ShplonkZ, Transcript,
+        CONST_PROOF_SIZE_LOG_N, NUMBER_OF_ALPHAS,
     },
     utils::coord_to_halves_be,
 };
@@ -21,7 +22,9 @@ fn push_point(buf: &mut Bytes, pt: &G1Point) {
     buf.extend_from_slice(&y_hi);
 }
 
-fn split_challenge(challenge: Fr) -> (Fr, Fr) {
+/// Split a challenge into `(low, high)` 128-bit halves: `low` is the last
+/// 16 bytes of its big-endian encoding, `high` the first 16.
+pub fn split_challenge(challenge: Fr) -> (Fr, Fr) {
     let challenge_bytes = challenge.to_bytes();
     let mut low_bytes = [0u8; 32];
     low_bytes[16..].copy_from_slice(&challenge_bytes[16..]);
@@ -41,19 +44,61 @@ fn u64_to_be32(x: u64) -> [u8; 32] {
     out
 }
 
-fn generate_eta_challenge(
+/// The header and public inputs that open the eta round, in absorption order.
+fn public_inputs_prefix(
     env: &Env,
-    proof: &Proof,
     public_inputs: &Bytes,
     circuit_size: u64,
     public_inputs_size: u64,
     pub_inputs_offset: u64,
-) -> (Fr, Fr, Fr, Fr) {
+) -> Bytes {
     let mut data = Bytes::new(env);
     data.extend_from_slice(&u64_to_be32(circuit_size));
     data.extend_from_slice(&u64_to_be32(public_inputs_size));
     data.extend_from_slice(&u64_to_be32(pub_inputs_offset));
     data.append(public_inputs);
+    data
+}
+
+/// Keccak-256 of exactly the bytes the transcript binds the public inputs
+/// with: `circuit_size`, `public_inputs_size` and `pub_inputs_offset` as
+/// 32-byte words, then `public_inputs`. Lets an application commit to the
+/// same binding (e.g. in an event) without deriving the whole transcript.
+///
+/// `public_inputs_size` includes the pairing point object, as in
+/// [`generate_transcript`].
+pub fn public_inputs_digest(
+    env: &Env,
+    public_inputs: &Bytes,
+    circuit_size: u64,
+    public_inputs_size: u64,
+    pub_inputs_offset: u64,
+) -> [u8; 32] {
+    hash32(&public_inputs_prefix(
+        env,
+        public_inputs,
+        circuit_size,
+        public_inputs_size,
+        pub_inputs_offset,
+    ))
+}
+
+fn generate_eta_challenge<F: FnMut(&'static str, usize, Fr, &Bytes)>(
+    env: &Env,
+    proof: &Proof,
+    public_inputs: &Bytes,
+    circuit_size: u64,
+    public_inputs_size: u64,
+    pub_inputs_offset: u64,
+    on_challenge: &mut F,
+) -> (Fr, Fr, Fr, Fr) {
+    let mut data = public_inputs_prefix(
+        env,
+        public_inputs,
+        circuit_size,
+        public_inputs_size,
+        pub_inputs_offset,
+    );
     for fr in &proof.pairing_point_object {
         data.extend_from_slice(&fr.to_bytes());
     }
@@ -63,17 +108,21 @@ fn generate_eta_challenge(
 
     let previous_challenge = hash_to_fr(&data);
     let (eta, eta_two) = split_challenge(previous_challenge);
+    on_challenge("eta", 0, eta, &data);
+    on_challenge("eta_two", 0, eta_two, &data);
     let prev_bytes = Bytes::from_array(env, &previous_challenge.to_bytes());
     let previous_challenge = hash_to_fr(&prev_bytes);
     let (eta_three, _) = split_challenge(previous_challenge);
+    on_challenge("eta_three", 0, eta_three, &prev_bytes);
 
     (eta, eta_two, eta_three, previous_challenge)
 }
 
-fn generate_beta_and_gamma_challenges(
+fn generate_beta_and_gamma_challenges<F: FnMut(&'static str, usize, Fr, &Bytes)>(
     env: &Env,
     previous_challenge: Fr,
     proof: &Proof,
+    on_challenge: &mut F,
 ) -> (Fr, Fr, Fr) {
     let mut data = Bytes::new(env);
     data.extend_from_slice(&previous_challenge.to_bytes());
@@ -86,13 +135,16 @@ fn generate_beta_and_gamma_challenges(
     }
     let next_previous_challenge = hash_to_fr(&data);
     let (beta, gamma) = split_challenge(next_previous_challenge);
+    on_challenge("beta", 0, beta, &data);
+    on_challenge("gamma", 0, gamma, &data);
     (beta, gamma, next_previous_challenge)
 }
 
-fn generate_alpha_challenges(
+fn generate_alpha_challenges<F: FnMut(&'static str, usize, Fr, &Bytes)>(
     env: &Env,
     previous_challenge: Fr,
     proof: &Proof,
+    on_challenge: &mut F,
 ) -> ([Fr; NUMBER_OF_ALPHAS], Fr) {
     let mut data = Bytes::new(env);
     data.extend_from_slice(&previous_challenge.to_bytes());
@@ -105,6 +157,8 @@ fn generate_alpha_challenges(
     let (a0, a1) = split_challenge(next_previous_challenge);
     alphas[0] = a0;
     alphas[1] = a1;
+    on_challenge("alpha", 0, a0, &data);
+    on_challenge("alpha", 1, a1, &data);
 
     for i in 1..(NUMBER_OF_ALPHAS / 2) {
         let next_bytes = Bytes::from_array(env, &next_previous_challenge.to_bytes());
@@ -112,6 +166,8 @@ fn generate_alpha_challenges(
         let (lo, hi) = split_challenge(next_previous_challenge);
         alphas[2 * i] = lo;
         alphas[2 * i + 1] = hi;
+        on_challenge("alpha", 2 * i, lo, &next_bytes);
+        on_challenge("alpha", 2 * i + 1, hi, &next_bytes);
     }
 
     if (NUMBER_OF_ALPHAS & 1) == 1 && NUMBER_OF_ALPHAS > 2 {
@@ -119,18 +175,20 @@ fn generate_alpha_challenges(
         next_previous_challenge = hash_to_fr(&next_bytes);
         let (last, _) = split_challenge(next_previous_challenge);
         alphas[NUMBER_OF_ALPHAS - 1] = last;
+        on_challenge("alpha", NUMBER_OF_ALPHAS - 1, last, &next_bytes);
     }
 
     (alphas, next_previous_challenge)
 }
 
-fn generate_relation_parameters_challenges(
+fn generate_relation_parameters_challenges<F: FnMut(&'static str, usize, Fr, &Bytes)>(
     env: &Env,
     proof: &Proof,
     public_inputs: &Bytes,
     circuit_size: u64,
     public_inputs_size: u64,
     pub_inputs_offset: u64,
+    on_challenge: &mut F,
 ) -> (RelationParameters, Fr) {
     let (eta, eta_two, eta_three, previous_challenge) = generate_eta_challenge(
         env,
@@ -139,9 +197,10 @@ fn generate_relation_parameters_challenges(
         circuit_size,
         public_inputs_size,
         pub_inputs_offset,
+        on_challenge,
     );
     let (beta, gamma, next_previous_challenge) =
-        generate_beta_and_gamma_challenges(env, previous_challenge, proof);
+        generate_beta_and_gamma_challenges(env, previous_challenge, proof, on_challenge);
     let rp = RelationParameters {
         eta,
         eta_two,
@@ -153,9 +212,10 @@ fn generate_relation_parameters_challenges(
     (rp, next_previous_challenge)
 }
 
-fn generate_gate_challenges(
+fn generate_gate_challenges<F: FnMut(&'static str, usize, Fr, &Bytes)>(
     env: &Env,
     previous_challenge: Fr,
+    on_challenge: &mut F,
 ) -> ([Fr; CONST_PROOF_SIZE_LOG_N], Fr) {
     let mut next_previous_challenge = previous_challenge;
     let mut gate_challenges = [Fr::zero(); CONST_PROOF_SIZE_LOG_N];
@@ -163,14 +223,16 @@ fn generate_gate_challenges(
         let next_bytes = Bytes::from_array(env, &next_previous_challenge.to_bytes());
         next_previous_challenge = hash_to_fr(&next_bytes);
         gate_challenges[i] = split_challenge(next_previous_challenge).0;
+        on_challenge("gate_challenge", i, gate_challenges[i], &next_bytes);
     }
     (gate_challenges, next_previous_challenge)
 }
 
-fn generate_sumcheck_challenges(
+fn generate_sumcheck_challenges<F: FnMut(&'static str, usize, Fr, &Bytes)>(
     env: &Env,
     proof: &Proof,
     previous_challenge: Fr,
+    on_challenge: &mut F,
 ) -> ([Fr; CONST_PROOF_SIZE_LOG_N], Fr) {
     let mut next_previous_challenge = previous_challenge;
     let mut sumcheck_challenges = [Fr::zero(); CONST_PROOF_SIZE_LOG_N];
@@ -182,11 +244,17 @@ fn generate_sumcheck_challenges(
         }
         next_previous_challenge = hash_to_fr(&data);
         sumcheck_challenges[r] = split_challenge(next_previous_challenge).0;
+        on_challenge("sumcheck_u", r, sumcheck_challenges[r], &data);
     }
     (sumcheck_challenges, next_previous_challenge)
 }
 
-fn generate_rho_challenge(env: &Env, proof: &Proof, previous_challenge: Fr) -> (Fr, Fr) {
+fn generate_rho_challenge<F: FnMut(&'static str, usize, Fr, &Bytes)>(
+    env: &Env,
+    proof: &Proof,
+    previous_challenge: Fr,
+    on_challenge: &mut F,
+) -> (Fr, Fr) {
     let mut data = Bytes::new(env);
     data.extend_from_slice(&previous_challenge.to_bytes());
     for &e in proof.sumcheck_evaluations.iter() {
@@ -194,10 +262,16 @@ fn generate_rho_challenge(env: &Env, proof: &Proof, previous_challenge: Fr) -> (
     }
     let next_previous_challenge = hash_to_fr(&data);
     let rho = split_challenge(next_previous_challenge).0;
+    on_challenge("rho", 0, rho, &data);
     (rho, next_previous_challenge)
 }
 
-fn generate_gemini_r_challenge(env: &Env, proof: &Proof, previous_challenge: Fr) -> (Fr, Fr) {
+fn generate_gemini_r_challenge<F: FnMut(&'static str, usize, Fr, &Bytes)>(
+    env: &Env,
+    proof: &Proof,
+    previous_challenge: Fr,
+    on_challenge: &mut F,
+) -> (Fr, Fr) {
     let mut data = Bytes::new(env);
     data.extend_from_slice(&previous_challenge.to_bytes());
     for pt in proof.gemini_fold_comms.iter() {
@@ -205,10 +279,16 @@ fn generate_gemini_r_challenge(env: &Env, proof: &Proof, previous_challenge: Fr)
     }
     let next_previous_challenge = hash_to_fr(&data);
     let gemini_r = split_challenge(next_previous_challenge).0;
+    on_challenge("gemini_r", 0, gemini_r, &data);
     (gemini_r, next_previous_challenge)
 }
 
-fn generate_shplonk_nu_challenge(env: &Env, proof: &Proof, previous_challenge: Fr) -> (Fr, Fr) {
+fn generate_shplonk_nu_challenge<F: FnMut(&'static str, usize, Fr, &Bytes)>(
+    env: &Env,
+    proof: &Proof,
+    previous_challenge: Fr,
+    on_challenge: &mut F,
+) -> (Fr, Fr) {
     let mut data = Bytes::new(env);
     data.extend_from_slice(&previous_challenge.to_bytes());
     for &a in proof.gemini_a_evaluations.iter() {
@@ -216,18 +296,48 @@ fn generate_shplonk_nu_challenge(env: &Env, proof: &Proof, previous_challenge: F
     }
     let next_previous_challenge = hash_to_fr(&data);
     let shplonk_nu = split_challenge(next_previous_challenge).0;
+    on_challenge("shplonk_nu", 0, shplonk_nu, &data);
     (shplonk_nu, next_previous_challenge)
 }
 
-fn generate_shplonk_z_challenge(env: &Env, proof: &Proof, previous_challenge: Fr) -> (Fr, Fr) {
+fn generate_shplonk_z_challenge<F: FnMut(&'static str, usize, Fr, &Bytes)>(
+    env: &Env,
+    proof: &Proof,
+    previous_challenge: Fr,
+    on_challenge: &mut F,
+) -> (Fr, Fr) {
     let mut data = Bytes::new(env);
     data.extend_from_slice(&previous_challenge.to_bytes());
     push_point(&mut data, &proof.shplonk_q);
     let next_previous_challenge = hash_to_fr(&data);
     let shplonk_z = split_challenge(next_previous_challenge).0;
+    on_challenge("shplonk_z", 0, shplonk_z, &data);
     (shplonk_z, next_previous_challenge)
 }
 
+/// Keccak-256 of every challenge in `t`, latest first: `shplonk_z`,
+/// `shplonk_nu`, `gemini_r`, `rho`, the sum-check `u` challenges, the gate
+/// challenges, the alphas, then `eta`, `eta_two`, `eta_three`, `beta` and
+/// `gamma`, as 32-byte big-endian words. `public_inputs_delta` is derived
+/// rather than challenged, so it is left out.
+///
+/// Anchors an accepted verification to its challenge set, which an audit
+/// can re-derive with [`generate_transcript`] and compare.
+pub fn challenge_digest(env: &Env, t: &Transcript) -> [u8; 32] {
+    let rp = &t.rel_params;
+    let mut data = Bytes::new(env);
+    for fr in [t.shplonk_z.0, t.shplonk_nu.0, t.gemini_r.0, t.rho.0]
+        .iter()
+        .chain(&t.sumcheck_u_challenges)
+        .chain(&t.gate_challenges)
+        .chain(&t.alphas)
+        .chain(&[rp.eta, rp.eta_two, rp.eta_three, rp.beta, rp.gamma])
+    {
+        data.extend_from_slice(&fr.to_bytes());
+    }
+    hash32(&data)
+}
+
 pub fn generate_transcript(
     env: &Env,
     proof: &Proof,
@@ -235,6 +345,31 @@ pub fn generate_transcript(
     circuit_size: u64,
     public_inputs_size: u64,
     pub_inputs_offset: u64,
+) -> Transcript {
+    generate_transcript_with(
+        env,
+        proof,
+        public_inputs,
+        circuit_size,
+        public_inputs_size,
+        pub_inputs_offset,
+        &mut |_, _, _, _| {},
+    )
+}
+
+/// Same as [`generate_transcript`], but reports every challenge as it is
+/// derived: `on_challenge(name, index, value, absorbed)`, where `absorbed`
+/// is the exact byte string hashed for that round. Challenges are reported
+/// in derivation order; `index` is 0 except for `alpha`, `gate_challenge`
+/// and `sumcheck_u`.
+pub fn generate_transcript_with<F: FnMut(&'static str, usize, Fr, &Bytes)>(
+    env: &Env,
+    proof: &Proof,
+    public_inputs: &Bytes,
+    circuit_size: u64,
+    public_inputs_size: u64,
+    pub_inputs_offset: u64,
+    on_challenge: &mut F,
 ) -> Transcript {
     // 1) eta/beta/gamma
     let (rp, previous_challenge) = generate_relation_parameters_challenges(
@@ -244,43 +379,68 @@ pub fn generate_transcript(
         circuit_size,
         public_inputs_size,
         pub_inputs_offset,
+        on_challenge,
     );
 
     // 2) alphas
-    let (alphas, previous_challenge) = generate_alpha_challenges(env, previous_challenge, proof);
+    let (alphas, previous_challenge) =
+        generate_alpha_challenges(env, previous_challenge, proof, on_challenge);
 
     // 3) gate challenges
-    let (gate_chals, previous_challenge) = generate_gate_challenges(env, previous_challenge);
+    let (gate_chals, previous_challenge) =
+        generate_gate_challenges(env, previous_challenge, on_challenge);
 
     // 4) sumcheck challenges
     let (u_chals, previous_challenge) =
-        generate_sumcheck_challenges(env, proof, previous_challenge);
+        generate_sumcheck_challenges(env, proof, previous_challenge, on_challenge);
 
     // 5) rho
-    let (rho, previous_challenge) = generate_rho_challenge(env, proof, previous_challenge);
+    let (rho, previous_challenge) =
+        generate_rho_challenge(env, proof, previous_challenge, on_challenge);
 
     // 6) gemini_r
     let (gemini_r, previous_challenge) =
-        generate_gemini_r_challenge(env, proof, previous_challenge);
+        generate_gemini_r_challenge(env, proof, previous_challenge, on_challenge);
 
     // 7) shplonk_nu
     let (shplonk_nu, previous_challenge) =
-        generate_shplonk_nu_challenge(env, proof, previous_challenge);
+        generate_shplonk_nu_challenge(env, proof, previous_challenge, on_challenge);
 
     // 8) shplonk_z
     let (shplonk_z, _previous_challenge) =
-        generate_shplonk_z_challenge(env, proof, previous_challenge);
+        generate_shplonk_z_challenge(env, proof, previous_challenge, on_challenge);
 
     trace!("===== TRANSCRIPT PARAMETERS =====");
-    trace!("eta = 0x{}", hex::encode(rp.eta.to_bytes()));
-    trace!("eta_two = 0x{}", hex::encode(rp.eta_two.to_bytes()));
-    trace!("eta_three = 0x{}", hex::encode(rp.eta_three.to_bytes()));
-    trace!("beta = 0x{}", hex::encode(rp.beta.to_bytes()));
-    trace!("gamma = 0x{}", hex::encode(rp.gamma.to_bytes()));
-    trace!("rho = 0x{}", hex::encode(rho.to_bytes()));
-    trace!("gemini_r = 0x{}", hex::encode(gemini_r.to_bytes()));
-    trace!("shplonk_nu = 0x{}", hex::encode(shplonk_nu.to_bytes()));
-    trace!("shplonk_z = 0x{}", hex::encode(shplonk_z.to_bytes()));
+    trace!("eta = 0x{}", crate::debug::bytes_to_hex(&rp.eta.to_bytes()));
+    trace!(
+        "eta_two = 0x{}",
+        crate::debug::bytes_to_hex(&rp.eta_two.to_bytes())
+    );
+    trace!(
+        "eta_three = 0x{}",
+        crate::debug::bytes_to_hex(&rp.eta_three.to_bytes())
+    );
+    trace!(
+        "beta = 0x{}",
+        crate::debug::bytes_to_hex(&rp.beta.to_bytes())
+    );
+    trace!(
+        "gamma = 0x{}",
+        crate::debug::bytes_to_hex(&rp.gamma.to_bytes())
+    );
+    trace!("rho = 0x{}", crate::debug::bytes_to_hex(&rho.to_bytes()));
+    trace!(
+        "gemini_r = 0x{}",
+        crate::debug::bytes_to_hex(&gemini_r.to_bytes())
+    );
+    trace!(
+        "shplonk_nu = 0x{}",
+        crate::debug::bytes_to_hex(&shplonk_nu.to_bytes())
+    );
+    trace!(
+        "shplonk_z = 0x{}",
+        crate::debug::bytes_to_hex(&shplonk_z.to_bytes())
+    );
     trace!("circuit_size = {}", circuit_size);
     trace!("public_inputs_total = {}", public_inputs_size);
     trace!("public_inputs_offset = {}", pub_inputs_offset);
@@ -291,9 +451,9 @@ pub fn generate_transcript(
         alphas,
         gate_challenges: gate_chals,
         sumcheck_u_challenges: u_chals,
-        rho,
-        gemini_r,
-        shplonk_nu,
-        shplonk_z,
+        rho: Rho(rho),
+        gemini_r: GeminiR(gemini_r),
+        shplonk_nu: ShplonkNu(shplonk_nu),
+        shplonk_z: ShplonkZ(shplonk_z),
     }
 }
@@ -1,13 +1,28 @@
 use crate::field::Fr;
+use ark_ff::{BigInteger, PrimeField, Zero};
 
+#[cfg(not(feature = "std"))]
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+
+/// Sum-check rounds a proof is padded to, whatever the circuit size.
 pub const CONST_PROOF_SIZE_LOG_N: usize = 28;
+/// Subrelations across all relations; see [`crate::relations::SUBRELATION_NAMES`].
 pub const NUMBER_OF_SUBRELATIONS: usize = 26;
+/// Evaluations per sum-check round univariate.
 pub const BATCHED_RELATION_PARTIAL_LENGTH: usize = 8;
+/// Polynomial evaluations in a proof: unshifted, then shifted.
 pub const NUMBER_OF_ENTITIES: usize = 40;
 pub const NUMBER_UNSHIFTED: usize = 35;
 pub const NUMBER_TO_BE_SHIFTED: usize = 5;
+/// Field limbs of the pairing point object leading the proof.
 pub const PAIRING_POINTS_SIZE: usize = 16;
 pub const NUMBER_OF_ALPHAS: usize = NUMBER_OF_SUBRELATIONS - 1;
+/// Commitments in a VK, in serialization order (`qm` … `lagrange_last`).
+pub const VK_NUM_POINTS: usize = 27;
+
+const _: () = assert!(NUMBER_UNSHIFTED + NUMBER_TO_BE_SHIFTED == NUMBER_OF_ENTITIES);
 
 /// Wire indices for the Ultra Honk protocol.
 #[derive(Copy, Clone, Debug)]
@@ -60,6 +75,12 @@ impl Wire {
     }
 }
 
+/// BN254 base field modulus p, big-endian.
+const BN254_FQ_MODULUS_BE: [u8; 32] = [
+    0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29, 0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81, 0x58, 0x5d,
+    0x97, 0x81, 0x6a, 0x91, 0x68, 0x71, 0xca, 0x8d, 0x3c, 0x20, 0x8c, 0x16, 0xd8, 0x7c, 0xfd, 0x47,
+];
+
 /// A G1 point in affine coordinates.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct G1Point {
@@ -94,6 +115,27 @@ impl G1Point {
         }
     }
 
+    /// Both coordinates are below the BN254 base field modulus. The host
+    /// rejects anything else by trapping, so loaders check first.
+    pub fn is_canonical(&self) -> bool {
+        self.x < BN254_FQ_MODULUS_BE && self.y < BN254_FQ_MODULUS_BE
+    }
+
+    /// On BN254 (`y² = x³ + 3`) with canonical coordinates, or the all-zero
+    /// identity. The host traps on anything else inside `g1_mul`, so callers
+    /// that want to name the offending point check this first.
+    pub fn is_on_curve(&self) -> bool {
+        if *self == G1Point::infinity() {
+            return true;
+        }
+        if !self.is_canonical() {
+            return false;
+        }
+        let x = ark_bn254::Fq::from_be_bytes_mod_order(&self.x);
+        let y = ark_bn254::Fq::from_be_bytes_mod_order(&self.y);
+        ark_bn254::G1Affine::new_unchecked(x, y).is_on_curve()
+    }
+
     pub fn generator() -> Self {
         let mut x = [0u8; 32];
         let mut y = [0u8; 32];
@@ -110,11 +152,14 @@ impl Default for G1Point {
 }
 
 /// The verification key structure
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct VerificationKey {
     pub circuit_size: u64,
     pub log_circuit_size: u64,
     pub public_inputs_size: u64,
+    /// Row of the first public input; bb v0.87 always writes 1, past the
+    /// zero row.
+    pub pub_inputs_offset: u64,
     // Selectors and wire commitments:
     pub qm: G1Point,
     pub qc: G1Point,
@@ -148,8 +193,178 @@ pub struct VerificationKey {
     pub lagrange_last: G1Point,
 }
 
+/// bb release a proof was produced with. Each variant fixes the transcript
+/// hash, the coordinate limb split and the pairing point object size as one
+/// bundle, so a verifier cannot mix settings from different releases.
+///
+/// Only v0.87 is implemented; other releases get a variant once their
+/// parameters and fixtures are in the tree.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum BbVersion {
+    /// Keccak-256 transcript, 136-bit low limbs, 16-element pairing point object.
+    #[default]
+    V0_87,
+}
+
+impl BbVersion {
+    /// Number of public-input slots taken by the pairing point object.
+    pub const fn pairing_points_size(self) -> usize {
+        match self {
+            BbVersion::V0_87 => PAIRING_POINTS_SIZE,
+        }
+    }
+
+    /// Bit width of the low limb when a coordinate is split for the transcript.
+    pub const fn limb_split_bits(self) -> u32 {
+        match self {
+            BbVersion::V0_87 => 136,
+        }
+    }
+
+    /// Hash the Fiat–Shamir transcript is built with.
+    pub const fn transcript_hash(self) -> &'static str {
+        match self {
+            BbVersion::V0_87 => "keccak256",
+        }
+    }
+}
+
+/// Best-effort findings about the VK's `lagrange_first`/`lagrange_last`
+/// commitments. These pass byte parsing but make verification fail opaquely.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum LagrangeWarning {
+    /// `lagrange_first` is the point at infinity for a nonzero-size circuit.
+    FirstIsIdentity,
+    /// `lagrange_last` is the point at infinity for a nonzero-size circuit.
+    LastIsIdentity,
+    /// Both commitments coincide although the circuit has more than one row.
+    FirstEqualsLast,
+    /// A commitment equals the SRS generator, which looks like a placeholder.
+    EqualsGenerator,
+}
+
+impl VerificationKey {
+    /// Build a VK in memory, for embedders that don't start from bb bytes.
+    ///
+    /// `points` are in the same order as the serialized VK (`qm`, `qc`, …,
+    /// `lagrange_first`, `lagrange_last`). `circuit_size` must be a power of
+    /// two within `CONST_PROOF_SIZE_LOG_N`, and `public_inputs_size` counts
+    /// the pairing point object. Points are checked by the host when used.
+    /// `pub_inputs_offset` is set to bb's 1.
+    pub fn from_points(
+        circuit_size: u64,
+        public_inputs_size: u64,
+        points: [G1Point; VK_NUM_POINTS],
+    ) -> Result<Self, &'static str> {
+        if circuit_size < 2 || !circuit_size.is_power_of_two() {
+            return Err("circuit size must be a power of two");
+        }
+        let log_circuit_size = circuit_size.trailing_zeros() as u64;
+        if log_circuit_size > CONST_PROOF_SIZE_LOG_N as u64 {
+            return Err("circuit size exceeds CONST_PROOF_SIZE_LOG_N");
+        }
+        if public_inputs_size < PAIRING_POINTS_SIZE as u64 {
+            return Err("public inputs must include the pairing point object");
+        }
+        if !points.iter().all(G1Point::is_canonical) {
+            return Err("vk coordinate is not below the base field modulus");
+        }
+        let [qm, qc, ql, qr, qo, q4, q_lookup, q_arith, q_delta_range, q_elliptic, q_aux, q_poseidon2_external, q_poseidon2_internal, s1, s2, s3, s4, id1, id2, id3, id4, t1, t2, t3, t4, lagrange_first, lagrange_last] =
+            points;
+        Ok(VerificationKey {
+            circuit_size,
+            log_circuit_size,
+            public_inputs_size,
+            pub_inputs_offset: 1,
+            qm,
+            qc,
+            ql,
+            qr,
+            qo,
+            q4,
+            q_lookup,
+            q_arith,
+            q_delta_range,
+            q_elliptic,
+            q_aux,
+            q_poseidon2_external,
+            q_poseidon2_internal,
+            s1,
+            s2,
+            s3,
+            s4,
+            id1,
+            id2,
+            id3,
+            id4,
+            t1,
+            t2,
+            t3,
+            t4,
+            lagrange_first,
+            lagrange_last,
+        })
+    }
+
+    /// The commitments in serialization order; the inverse of [`Self::from_points`].
+    pub fn points(&self) -> [G1Point; VK_NUM_POINTS] {
+        [
+            self.qm,
+            self.qc,
+            self.ql,
+            self.qr,
+            self.qo,
+            self.q4,
+            self.q_lookup,
+            self.q_arith,
+            self.q_delta_range,
+            self.q_elliptic,
+            self.q_aux,
+            self.q_poseidon2_external,
+            self.q_poseidon2_internal,
+            self.s1,
+            self.s2,
+            self.s3,
+            self.s4,
+            self.id1,
+            self.id2,
+            self.id3,
+            self.id4,
+            self.t1,
+            self.t2,
+            self.t3,
+            self.t4,
+            self.lagrange_first,
+            self.lagrange_last,
+        ]
+    }
+
+    /// Sanity-check the first/last-row indicator commitments against
+    /// `circuit_size` and the SRS `generator`. Returns the first finding;
+    /// `None` does not prove the commitments are correct.
+    pub fn lagrange_sanity(&self, generator: &G1Point) -> Option<LagrangeWarning> {
+        if self.circuit_size == 0 {
+            return None;
+        }
+        let infinity = G1Point::infinity();
+        if self.lagrange_first == infinity {
+            return Some(LagrangeWarning::FirstIsIdentity);
+        }
+        if self.lagrange_last == infinity {
+            return Some(LagrangeWarning::LastIsIdentity);
+        }
+        if self.circuit_size > 1 && self.lagrange_first == self.lagrange_last {
+            return Some(LagrangeWarning::FirstEqualsLast);
+        }
+        if self.lagrange_first == *generator || self.lagrange_last == *generator {
+            return Some(LagrangeWarning::EqualsGenerator);
+        }
+        None
+    }
+}
+
 /// The Proof structure
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Proof {
     // Pairing point object (16 Fr elements)
     pub pairing_point_object: [Fr; PAIRING_POINTS_SIZE],
@@ -174,6 +389,181 @@ pub struct Proof {
     pub kzg_quotient: G1Point,
 }
 
+/// Bits per limb of a pairing point object coordinate.
+const PAIRING_POINT_LIMB_BITS: usize = 68;
+
+impl Proof {
+    /// Check the proof's arrays cover `log_n` rounds: sum-check reads
+    /// `log_n` univariates and Gemini reads `log_n` evaluations and
+    /// `log_n - 1` fold commitments. Sum-check and Shplemini index without
+    /// further checks once this has passed.
+    pub fn validate_dimensions(&self, log_n: u64) -> Result<(), &'static str> {
+        let log_n = usize::try_from(log_n).map_err(|_| "log_n out of range")?;
+        if log_n == 0 {
+            return Err("log_n must be at least 1");
+        }
+        if self.sumcheck_univariates.len() < log_n {
+            return Err("too few sumcheck univariates for log_n");
+        }
+        if self.gemini_a_evaluations.len() < log_n {
+            return Err("too few gemini evaluations for log_n");
+        }
+        if self.gemini_fold_comms.len() < log_n - 1 {
+            return Err("too few gemini fold commitments for log_n");
+        }
+        Ok(())
+    }
+
+    /// Check every commitment is [canonical](G1Point::is_canonical).
+    pub fn validate_coordinates(&self) -> Result<(), &'static str> {
+        let canonical = self.components().iter().all(|(_, c)| match c {
+            ProofComponent::Points(points) => points.iter().all(G1Point::is_canonical),
+            ProofComponent::Scalars(_) => true,
+        });
+        if canonical {
+            Ok(())
+        } else {
+            Err("proof coordinate is not below the base field modulus")
+        }
+    }
+
+    /// Decode `pairing_point_object` into the two G1 points it encodes
+    /// (`P0.x`, `P0.y`, `P1.x`, `P1.y`; four 68-bit little-endian limbs each)
+    /// and check both lie on BN254. All-zero limbs decode to the identity.
+    pub fn pairing_points(&self) -> Result<(G1Point, G1Point), &'static str> {
+        let coord = |i: usize| -> Result<ark_bn254::Fq, &'static str> {
+            let mut words = [0u64; 4];
+            for (j, limb) in self.pairing_point_object[4 * i..4 * i + 4]
+                .iter()
+                .enumerate()
+            {
+                let bytes = limb.to_bytes();
+                if bytes[..16] != [0u8; 16] {
+                    return Err("pairing point limb exceeds 68 bits");
+                }
+                let mut low = [0u8; 16];
+                low.copy_from_slice(&bytes[16..]);
+                let value = u128::from_be_bytes(low);
+                if value >> PAIRING_POINT_LIMB_BITS != 0 {
+                    return Err("pairing point limb exceeds 68 bits");
+                }
+                // Limbs occupy disjoint bit ranges, so OR-ing them in is exact.
+                let shift = PAIRING_POINT_LIMB_BITS * j;
+                let (word, bit) = (shift / 64, shift % 64);
+                words[word] |= (value << bit) as u64;
+                let mut rest = value >> (64 - bit);
+                for w in words.iter_mut().skip(word + 1) {
+                    if rest == 0 {
+                        break;
+                    }
+                    *w |= rest as u64;
+                    rest >>= 64;
+                }
+                if rest != 0 {
+                    return Err("pairing point coordinate exceeds 256 bits");
+                }
+            }
+            ark_bn254::Fq::from_bigint(ark_ff::BigInt(words))
+                .ok_or("pairing point coordinate is not reduced")
+        };
+        let point = |i: usize| -> Result<G1Point, &'static str> {
+            let (x, y) = (coord(2 * i)?, coord(2 * i + 1)?);
+            if x.is_zero() && y.is_zero() {
+                return Ok(G1Point::infinity());
+            }
+            if !ark_bn254::G1Affine::new_unchecked(x, y).is_on_curve() {
+                return Err("pairing point is not on the curve");
+            }
+            let be = |f: ark_bn254::Fq| {
+                let mut out = [0u8; 32];
+                out.copy_from_slice(&f.into_bigint().to_bytes_be());
+                out
+            };
+            Ok(G1Point::from_xy(be(x), be(y)))
+        };
+        Ok((point(0)?, point(1)?))
+    }
+
+    /// Every field of the proof with its name, in wire order. Sum-check
+    /// univariates are flattened round by round.
+    pub fn components(&self) -> [(&'static str, ProofComponent<'_>); 15] {
+        use core::slice::from_ref;
+        use ProofComponent::{Points, Scalars};
+        [
+            ("pairing_point_object", Scalars(&self.pairing_point_object)),
+            ("w1", Points(from_ref(&self.w1))),
+            ("w2", Points(from_ref(&self.w2))),
+            ("w3", Points(from_ref(&self.w3))),
+            (
+                "lookup_read_counts",
+                Points(from_ref(&self.lookup_read_counts)),
+            ),
+            ("lookup_read_tags", Points(from_ref(&self.lookup_read_tags))),
+            ("w4", Points(from_ref(&self.w4))),
+            ("lookup_inverses", Points(from_ref(&self.lookup_inverses))),
+            ("z_perm", Points(from_ref(&self.z_perm))),
+            (
+                "sumcheck_univariates",
+                Scalars(self.sumcheck_univariates.as_flattened()),
+            ),
+            ("sumcheck_evaluations", Scalars(&self.sumcheck_evaluations)),
+            ("gemini_fold_comms", Points(&self.gemini_fold_comms)),
+            ("gemini_a_evaluations", Scalars(&self.gemini_a_evaluations)),
+            ("shplonk_q", Points(from_ref(&self.shplonk_q))),
+            ("kzg_quotient", Points(from_ref(&self.kzg_quotient))),
+        ]
+    }
+}
+
+/// The values of one named [`Proof`] field.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProofComponent<'a> {
+    Scalars(&'a [Fr]),
+    Points(&'a [G1Point]),
+}
+
+/// Circuit order of named public inputs, so callers can pass them by name
+/// and the canonical byte order the transcript binds is built in one place.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PublicInputLayout {
+    names: Vec<String>,
+}
+
+impl PublicInputLayout {
+    /// `names` in the order the circuit declares its public inputs.
+    pub fn new(names: &[&str]) -> Result<Self, &'static str> {
+        for (i, name) in names.iter().enumerate() {
+            if names[..i].contains(name) {
+                return Err("duplicate public input name");
+            }
+        }
+        Ok(Self {
+            names: names.iter().map(|n| String::from(*n)).collect(),
+        })
+    }
+
+    /// Position of `name` in the circuit's public inputs.
+    pub fn position(&self, name: &str) -> Option<usize> {
+        self.names.iter().position(|n| n == name)
+    }
+
+    /// Concatenate `inputs` as 32-byte big-endian words in layout order.
+    /// Every name must be present exactly once, with no extras.
+    pub fn assemble(&self, inputs: &BTreeMap<String, Fr>) -> Result<Vec<u8>, &'static str> {
+        if inputs.len() != self.names.len() {
+            return Err("public input names do not match the layout");
+        }
+        let mut out = Vec::with_capacity(32 * self.names.len());
+        for name in &self.names {
+            let value = inputs
+                .get(name)
+                .ok_or("public input names do not match the layout")?;
+            out.extend_from_slice(&value.to_bytes());
+        }
+        Ok(out)
+    }
+}
+
 /// Relation parameters (η, η₂, η₃, β, γ, public_inputs_delta).
 #[derive(Clone, Debug)]
 pub struct RelationParameters {
@@ -185,15 +575,39 @@ pub struct RelationParameters {
     pub public_inputs_delta: Fr,
 }
 
+macro_rules! challenge {
+    ($($(#[$doc:meta])* $name:ident;)*) => {
+        $(
+            $(#[$doc])*
+            #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+            pub struct $name(pub Fr);
+        )*
+    };
+}
+
+challenge! {
+    /// ρ, batching the entity evaluations in Shplemini.
+    Rho;
+    /// Gemini evaluation point r.
+    GeminiR;
+    /// Shplonk batching challenge ν.
+    ShplonkNu;
+    /// Shplonk opening point z.
+    ShplonkZ;
+}
+
 /// The transcript holding all Fiat–Shamir challenges.
+///
+/// The Shplemini challenges are wrapped so they cannot be passed for one
+/// another; unwrap them by pattern (`let GeminiR(r) = tp.gemini_r;`).
 #[derive(Clone, Debug)]
 pub struct Transcript {
     pub rel_params: RelationParameters,
     pub alphas: [Fr; NUMBER_OF_ALPHAS],
     pub gate_challenges: [Fr; CONST_PROOF_SIZE_LOG_N],
     pub sumcheck_u_challenges: [Fr; CONST_PROOF_SIZE_LOG_N],
-    pub rho: Fr,
-    pub gemini_r: Fr,
-    pub shplonk_nu: Fr,
-    pub shplonk_z: Fr,
+    pub rho: Rho,
+    pub gemini_r: GeminiR,
+    pub shplonk_nu: ShplonkNu,
+    pub shplonk_z: ShplonkZ,
 }
@@ -1,18 +1,19 @@
 //! Utilities for loading Proof and VerificationKey, plus byte↔field/point conversion.
 
 use crate::field::Fr;
+#[cfg(feature = "soroban")]
+use crate::hash::hash32;
 use crate::types::{
-    G1Point, Proof, VerificationKey, BATCHED_RELATION_PARTIAL_LENGTH, CONST_PROOF_SIZE_LOG_N,
-    NUMBER_OF_ENTITIES, PAIRING_POINTS_SIZE,
+    G1Point, Proof, ProofComponent, VerificationKey, BATCHED_RELATION_PARTIAL_LENGTH,
+    CONST_PROOF_SIZE_LOG_N, NUMBER_OF_ENTITIES, PAIRING_POINTS_SIZE, VK_NUM_POINTS,
 };
-use crate::PROOF_BYTES;
-use core::array;
-use soroban_sdk::Bytes;
+use crate::{PROOF_BYTES, PROOF_FIELDS};
+use core::{array, ops::Range};
+#[cfg(feature = "soroban")]
+use soroban_sdk::{Bytes, Env, U256};
 
-/// Convert a 32-byte big-endian array into an Fr.
-fn bytes32_to_fr(bytes: &[u8; 32]) -> Fr {
-    Fr::from_bytes(bytes)
-}
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
 
 /// Split a 32-byte big-endian field element into (low136, high) limbs.
 pub fn coord_to_halves_be(coord: &[u8; 32]) -> ([u8; 32], [u8; 32]) {
@@ -23,14 +24,20 @@ pub fn coord_to_halves_be(coord: &[u8; 32]) -> ([u8; 32], [u8; 32]) {
     (low, high)
 }
 
-fn read_bytes<const N: usize>(bytes: &Bytes, idx: &mut u32) -> [u8; N] {
+fn read_bytes<const N: usize>(bytes: &[u8], idx: &mut usize) -> [u8; N] {
     let mut out = [0u8; N];
-    let end = *idx + N as u32;
-    bytes.slice(*idx..end).copy_into_slice(&mut out);
+    let end = *idx + N;
+    out.copy_from_slice(&bytes[*idx..end]);
     *idx = end;
     out
 }
 
+/// Whether `(lo, hi)` fit the widths [`coord_to_halves_be`] produces for a
+/// coordinate below p: `lo < 2^136` and `hi < 2^118`.
+fn limbs_fit(lo: &[u8; 32], hi: &[u8; 32]) -> bool {
+    lo[..15].iter().all(|&b| b == 0) && hi[..17].iter().all(|&b| b == 0) && hi[17] < 0x40
+}
+
 fn combine_limbs(lo: &[u8; 32], hi: &[u8; 32]) -> [u8; 32] {
     let mut out = [0u8; 32];
     out[..15].copy_from_slice(&hi[17..]);
@@ -38,76 +45,136 @@ fn combine_limbs(lo: &[u8; 32], hi: &[u8; 32]) -> [u8; 32] {
     out
 }
 
-/// Load a Proof from a byte array.
+/// Byte range of each [`Proof::components`] entry in a serialized proof, in
+/// order. Commitments take four 32-byte limbs, scalars one word.
+pub fn proof_layout() -> Vec<(&'static str, Range<usize>)> {
+    let proof = load_proof_from_slice(&[0u8; PROOF_BYTES]).expect("PROOF_BYTES is a valid length");
+    let mut start = 0;
+    proof
+        .components()
+        .iter()
+        .map(|(name, component)| {
+            let words = match component {
+                ProofComponent::Scalars(s) => s.len(),
+                ProofComponent::Points(p) => 4 * p.len(),
+            };
+            let range = start..start + 32 * words;
+            start = range.end;
+            (*name, range)
+        })
+        .collect()
+}
+
+/// Load a Proof from host bytes.
+///
+/// The host buffer is copied out once into a stack array and parsed from
+/// there, instead of issuing a host `slice` call per 32-byte word.
+///
+/// Fails, rather than panics, unless the input is exactly `PROOF_BYTES`.
+#[cfg(feature = "soroban")]
+pub fn load_proof(proof_bytes: &Bytes) -> Result<Proof, &'static str> {
+    if proof_bytes.len() as usize != PROOF_BYTES {
+        return Err("proof bytes len");
+    }
+    let mut buf = [0u8; PROOF_BYTES];
+    proof_bytes.copy_into_slice(&mut buf);
+    load_proof_from_slice(&buf)
+}
+
+/// Load a Proof from a byte slice.
 ///
 /// Note (bb v0.87.0): G1 coordinates are encoded as two limbs per coordinate
 /// using the (lo136, hi<=118) split and stored in the order (x_lo, x_hi, y_lo, y_hi).
-pub fn load_proof(proof_bytes: &Bytes) -> Proof {
-    assert_eq!(proof_bytes.len() as usize, PROOF_BYTES, "proof bytes len");
-    let mut boundary = 0u32;
+///
+/// Fails unless the input is exactly `PROOF_BYTES`; the layout below reads
+/// exactly that many bytes, so no read can then go out of bounds. Also fails
+/// if any scalar is `>= r`, as bb's own deserialization does, or any
+/// commitment limb is wider than its 136/118 bits, rather than accepting a
+/// second encoding of the same proof.
+pub fn load_proof_from_slice(proof_bytes: &[u8]) -> Result<Proof, &'static str> {
+    if proof_bytes.len() != PROOF_BYTES {
+        return Err("proof bytes len");
+    }
+    let mut boundary = 0usize;
+    let mut canonical = true;
+    let mut fits = true;
 
-    fn bytes_to_g1_proof_point(bytes: &Bytes, cur: &mut u32) -> G1Point {
+    // Helper: read a commitment's four limbs, clearing `fits` if any limb
+    // carries bits above its width
+    fn bytes_to_g1_proof_point(bytes: &[u8], cur: &mut usize, fits: &mut bool) -> G1Point {
         let x0 = read_bytes::<32>(bytes, cur);
         let x1 = read_bytes::<32>(bytes, cur);
         let y0 = read_bytes::<32>(bytes, cur);
         let y1 = read_bytes::<32>(bytes, cur);
+        *fits &= limbs_fit(&x0, &x1) && limbs_fit(&y0, &y1);
         let x = combine_limbs(&x0, &x1);
         let y = combine_limbs(&y0, &y1);
         G1Point { x, y }
     }
 
-    // Helper: bytesToFr (read next 32 bytes as Fr)
-    fn bytes_to_fr(bytes: &Bytes, cur: &mut u32) -> Fr {
+    // Helper: bytesToFr (read next 32 bytes as Fr), clearing `canonical`
+    // on a value >= r
+    fn bytes_to_fr(bytes: &[u8], cur: &mut usize, canonical: &mut bool) -> Fr {
         let arr = read_bytes::<32>(bytes, cur);
-        bytes32_to_fr(&arr)
+        Fr::from_bytes_canonical(&arr).unwrap_or_else(|| {
+            *canonical = false;
+            Fr::zero()
+        })
     }
 
     // 0) pairing point object
     let pairing_point_object: [Fr; PAIRING_POINTS_SIZE] =
-        array::from_fn(|_| bytes_to_fr(proof_bytes, &mut boundary));
+        array::from_fn(|_| bytes_to_fr(proof_bytes, &mut boundary, &mut canonical));
 
     // 1) w1, w2, w3
-    let w1 = bytes_to_g1_proof_point(proof_bytes, &mut boundary);
-    let w2 = bytes_to_g1_proof_point(proof_bytes, &mut boundary);
-    let w3 = bytes_to_g1_proof_point(proof_bytes, &mut boundary);
+    let w1 = bytes_to_g1_proof_point(proof_bytes, &mut boundary, &mut fits);
+    let w2 = bytes_to_g1_proof_point(proof_bytes, &mut boundary, &mut fits);
+    let w3 = bytes_to_g1_proof_point(proof_bytes, &mut boundary, &mut fits);
 
     // 2) lookup_read_counts, lookup_read_tags
-    let lookup_read_counts = bytes_to_g1_proof_point(proof_bytes, &mut boundary);
-    let lookup_read_tags = bytes_to_g1_proof_point(proof_bytes, &mut boundary);
+    let lookup_read_counts = bytes_to_g1_proof_point(proof_bytes, &mut boundary, &mut fits);
+    let lookup_read_tags = bytes_to_g1_proof_point(proof_bytes, &mut boundary, &mut fits);
 
     // 3) w4
-    let w4 = bytes_to_g1_proof_point(proof_bytes, &mut boundary);
+    let w4 = bytes_to_g1_proof_point(proof_bytes, &mut boundary, &mut fits);
 
     // 4) lookup_inverses, z_perm
-    let lookup_inverses = bytes_to_g1_proof_point(proof_bytes, &mut boundary);
-    let z_perm = bytes_to_g1_proof_point(proof_bytes, &mut boundary);
+    let lookup_inverses = bytes_to_g1_proof_point(proof_bytes, &mut boundary, &mut fits);
+    let z_perm = bytes_to_g1_proof_point(proof_bytes, &mut boundary, &mut fits);
 
     // 5) sumcheck_univariates
     let mut sumcheck_univariates =
         [[Fr::zero(); BATCHED_RELATION_PARTIAL_LENGTH]; CONST_PROOF_SIZE_LOG_N];
     for r in 0..CONST_PROOF_SIZE_LOG_N {
         for i in 0..BATCHED_RELATION_PARTIAL_LENGTH {
-            sumcheck_univariates[r][i] = bytes_to_fr(proof_bytes, &mut boundary);
+            sumcheck_univariates[r][i] = bytes_to_fr(proof_bytes, &mut boundary, &mut canonical);
         }
     }
 
     // 6) sumcheck_evaluations
     let sumcheck_evaluations: [Fr; NUMBER_OF_ENTITIES] =
-        array::from_fn(|_| bytes_to_fr(proof_bytes, &mut boundary));
+        array::from_fn(|_| bytes_to_fr(proof_bytes, &mut boundary, &mut canonical));
 
     // 7) gemini_fold_comms
     let gemini_fold_comms: [G1Point; CONST_PROOF_SIZE_LOG_N - 1] =
-        array::from_fn(|_| bytes_to_g1_proof_point(proof_bytes, &mut boundary));
+        array::from_fn(|_| bytes_to_g1_proof_point(proof_bytes, &mut boundary, &mut fits));
 
     // 8) gemini_a_evaluations
     let gemini_a_evaluations: [Fr; CONST_PROOF_SIZE_LOG_N] =
-        array::from_fn(|_| bytes_to_fr(proof_bytes, &mut boundary));
+        array::from_fn(|_| bytes_to_fr(proof_bytes, &mut boundary, &mut canonical));
 
     // 9) shplonk_q, kzg_quotient
-    let shplonk_q = bytes_to_g1_proof_point(proof_bytes, &mut boundary);
-    let kzg_quotient = bytes_to_g1_proof_point(proof_bytes, &mut boundary);
+    let shplonk_q = bytes_to_g1_proof_point(proof_bytes, &mut boundary, &mut fits);
+    let kzg_quotient = bytes_to_g1_proof_point(proof_bytes, &mut boundary, &mut fits);
+    debug_assert_eq!(boundary, PROOF_BYTES);
+    if !canonical {
+        return Err("proof scalar is not below the scalar field modulus");
+    }
+    if !fits {
+        return Err("proof commitment limb is wider than 136/118 bits");
+    }
 
-    Proof {
+    Ok(Proof {
         pairing_point_object,
         w1,
         w2,
@@ -123,33 +190,134 @@ pub fn load_proof(proof_bytes: &Bytes) -> Proof {
         gemini_a_evaluations,
         shplonk_q,
         kzg_quotient,
+    })
+}
+
+/// Encode a Proof in bb's wire layout; the inverse of
+/// [`load_proof_from_slice`], always `PROOF_BYTES` long.
+pub fn serialize_proof(proof: &Proof) -> Vec<u8> {
+    let mut out = Vec::with_capacity(PROOF_BYTES);
+    for (_, component) in proof.components() {
+        match component {
+            ProofComponent::Scalars(scalars) => {
+                for fr in scalars {
+                    out.extend_from_slice(&fr.to_bytes());
+                }
+            }
+            ProofComponent::Points(points) => {
+                for point in points {
+                    for coord in [&point.x, &point.y] {
+                        let (lo, hi) = coord_to_halves_be(coord);
+                        out.extend_from_slice(&lo);
+                        out.extend_from_slice(&hi);
+                    }
+                }
+            }
+        }
     }
+    debug_assert_eq!(out.len(), PROOF_BYTES);
+    out
 }
 
-/// Load a VerificationKey.
+/// Why [`load_proof_checked`] rejected a proof.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CheckedProofError {
+    /// The bytes did not parse; see [`load_proof_from_slice`].
+    Parse(&'static str),
+    /// The `index`-th commitment, counting G1 points in proof order (`w1` is
+    /// 0, `gemini_fold_comms[0]` is 8, `kzg_quotient` 36), is not on the curve.
+    InvalidPoint { index: usize, field: &'static str },
+}
+
+/// [`load_proof_from_slice`], then check that every commitment, padding
+/// included, is on the curve, so a garbage proof is named here rather than
+/// trapping in the MSM.
+///
+/// BN254 G1 has cofactor 1, so an on-curve point is already in the
+/// prime-order subgroup and needs no separate subgroup check.
+pub fn load_proof_checked(proof_bytes: &[u8]) -> Result<Proof, CheckedProofError> {
+    let proof = load_proof_from_slice(proof_bytes).map_err(CheckedProofError::Parse)?;
+    let mut index = 0;
+    for (field, component) in proof.components() {
+        if let ProofComponent::Points(points) = component {
+            for point in points {
+                if !point.is_on_curve() {
+                    return Err(CheckedProofError::InvalidPoint { index, field });
+                }
+                index += 1;
+            }
+        }
+    }
+    Ok(proof)
+}
+
+/// Convert a host `U256` into an Fr, reducing modulo the BN254 scalar field
+/// order r.
+///
+/// Values below r keep their value; values `>= r` wrap (e.g. r itself maps to
+/// zero), so `u256_from_fr(fr_from_u256(x)) != x` exactly when `x >= r`. Use
+/// [`fr_from_u256_checked`] where a silent wrap would be a bug.
+#[cfg(feature = "soroban")]
+pub fn fr_from_u256(x: &U256) -> Fr {
+    let mut be = [0u8; 32];
+    x.to_be_bytes().copy_into_slice(&mut be);
+    Fr::from_bytes(&be)
+}
+
+/// Like [`fr_from_u256`], but `None` if `x >= r` instead of reducing.
+#[cfg(feature = "soroban")]
+pub fn fr_from_u256_checked(env: &Env, x: &U256) -> Option<Fr> {
+    let fr = fr_from_u256(x);
+    (u256_from_fr(env, &fr) == *x).then_some(fr)
+}
+
+/// Convert an Fr into a host `U256` (its canonical value, always `< r`).
+#[cfg(feature = "soroban")]
+pub fn u256_from_fr(env: &Env, fr: &Fr) -> U256 {
+    U256::from_be_bytes(env, &Bytes::from_array(env, &fr.to_bytes()))
+}
+
+/// Byte length of a bb v0.87.0 VK: four u64 header words and 27 G1 points.
+///
+/// There is no recursion flag: v0.87 VKs dropped `contains_recursive_proof`
+/// and every proof carries the pairing point object, counted in
+/// `public_inputs_size` (see
+/// [`BbVersion::pairing_points_size`](crate::types::BbVersion::pairing_points_size)).
+pub const VK_BYTES: usize = 4 * 8 + VK_NUM_POINTS * 64;
+
+/// Load a VerificationKey from host bytes, copying them out once.
+#[cfg(feature = "soroban")]
 pub fn load_vk_from_bytes(bytes: &Bytes) -> Option<VerificationKey> {
-    const HEADER_WORDS: usize = 4;
-    const NUM_POINTS: usize = 27;
-    const EXPECTED_LEN: usize = HEADER_WORDS * 8 + NUM_POINTS * 64;
-    if bytes.len() as usize != EXPECTED_LEN {
+    if bytes.len() as usize != VK_BYTES {
+        return None;
+    }
+    let mut buf = [0u8; VK_BYTES];
+    bytes.copy_into_slice(&mut buf);
+    load_vk_from_slice(&buf)
+}
+
+/// Load a VerificationKey from a byte slice.
+pub fn load_vk_from_slice(bytes: &[u8]) -> Option<VerificationKey> {
+    if bytes.len() != VK_BYTES {
         return None;
     }
 
-    fn read_u64(bytes: &Bytes, idx: &mut u32) -> u64 {
+    fn read_u64(bytes: &[u8], idx: &mut usize) -> u64 {
         u64::from_be_bytes(read_bytes::<8>(bytes, idx))
     }
-    fn read_point(bytes: &Bytes, idx: &mut u32) -> Option<G1Point> {
+    fn read_point(bytes: &[u8], idx: &mut usize) -> Option<G1Point> {
         let x = read_bytes::<32>(bytes, idx);
         let y = read_bytes::<32>(bytes, idx);
-        // Curve, subgroup checks are executed in the Soroban host.
-        Some(G1Point { x, y })
+        // Curve, subgroup checks are executed in the Soroban host, which
+        // traps on a coordinate >= p instead of reducing it.
+        Some(G1Point { x, y }).filter(G1Point::is_canonical)
     }
 
-    let mut idx = 0u32;
+    let mut idx = 0usize;
     let circuit_size = read_u64(bytes, &mut idx);
     let log_circuit_size = read_u64(bytes, &mut idx);
     let public_inputs_size = read_u64(bytes, &mut idx);
-    let _pub_inputs_offset = read_u64(bytes, &mut idx);
+    let pub_inputs_offset = read_u64(bytes, &mut idx);
 
     let qm = read_point(bytes, &mut idx)?;
     let qc = read_point(bytes, &mut idx)?;
@@ -183,6 +351,7 @@ pub fn load_vk_from_bytes(bytes: &Bytes) -> Option<VerificationKey> {
         circuit_size,
         log_circuit_size,
         public_inputs_size,
+        pub_inputs_offset,
         qm,
         qc,
         ql,
@@ -212,3 +381,232 @@ pub fn load_vk_from_bytes(bytes: &Bytes) -> Option<VerificationKey> {
         lagrange_last,
     })
 }
+
+/// Serialize a VK in bb's binary layout; the inverse of [`load_vk_from_slice`].
+pub fn vk_to_bytes(vk: &VerificationKey) -> [u8; VK_BYTES] {
+    let mut out = [0u8; VK_BYTES];
+    let header = [
+        vk.circuit_size,
+        vk.log_circuit_size,
+        vk.public_inputs_size,
+        vk.pub_inputs_offset,
+    ];
+    for (chunk, v) in out[..32].chunks_exact_mut(8).zip(header) {
+        chunk.copy_from_slice(&v.to_be_bytes());
+    }
+    for (chunk, p) in out[32..].chunks_exact_mut(64).zip(vk.points()) {
+        chunk.copy_from_slice(&p.to_bytes());
+    }
+    out
+}
+
+/// Length of `abi.encode` of the Solidity verifier's `Honk.VerificationKey`.
+pub const SOLIDITY_VK_BYTES: usize = 3 * 32 + VK_NUM_POINTS * 64;
+
+/// `abi.encode(vk)` as bb v0.87's generated Solidity verifier lays out its
+/// `Honk.VerificationKey`: `circuitSize`, `logCircuitSize`,
+/// `publicInputsSize` as uint256, then each point as `(x, y)` in the order
+/// qm, qc, ql, qr, qo, q4, qLookup, qArith, qDeltaRange, qAux, qElliptic,
+/// qPoseidon2External, qPoseidon2Internal, s1..s4, id1..id4, t1..t4,
+/// lagrangeFirst, lagrangeLast. Note qAux precedes qElliptic there, unlike
+/// bb's binary VK.
+pub fn solidity_vk_encoding(vk: &VerificationKey) -> [u8; SOLIDITY_VK_BYTES] {
+    let mut out = [0u8; SOLIDITY_VK_BYTES];
+    for (i, v) in [vk.circuit_size, vk.log_circuit_size, vk.public_inputs_size]
+        .iter()
+        .enumerate()
+    {
+        out[i * 32 + 24..(i + 1) * 32].copy_from_slice(&v.to_be_bytes());
+    }
+    let points = [
+        &vk.qm,
+        &vk.qc,
+        &vk.ql,
+        &vk.qr,
+        &vk.qo,
+        &vk.q4,
+        &vk.q_lookup,
+        &vk.q_arith,
+        &vk.q_delta_range,
+        &vk.q_aux,
+        &vk.q_elliptic,
+        &vk.q_poseidon2_external,
+        &vk.q_poseidon2_internal,
+        &vk.s1,
+        &vk.s2,
+        &vk.s3,
+        &vk.s4,
+        &vk.id1,
+        &vk.id2,
+        &vk.id3,
+        &vk.id4,
+        &vk.t1,
+        &vk.t2,
+        &vk.t3,
+        &vk.t4,
+        &vk.lagrange_first,
+        &vk.lagrange_last,
+    ];
+    for (i, pt) in points.iter().enumerate() {
+        let at = 3 * 32 + i * 64;
+        out[at..at + 64].copy_from_slice(&pt.to_bytes());
+    }
+    out
+}
+
+/// Keccak-256 of [`solidity_vk_encoding`], i.e. `keccak256(abi.encode(vk))`
+/// on the EVM side, for a circuit id both chains can derive. This differs
+/// from the contract's `vk_id`, which hashes bb's binary VK bytes.
+#[cfg(feature = "soroban")]
+pub fn solidity_vk_hash(env: &Env, vk: &VerificationKey) -> [u8; 32] {
+    hash32(&Bytes::from_array(env, &solidity_vk_encoding(vk)))
+}
+
+/// Byte length of the big-endian field-count header of a [`ProofEnvelope`].
+pub const ENVELOPE_HEADER_BYTES: usize = 4;
+
+/// Borrowed view of the canonical `[u32 total_fields][public_inputs][proof]`
+/// envelope, where `total_fields` counts the 32-byte words that follow.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ProofEnvelope<'a> {
+    pub public_inputs: &'a [u8],
+    pub proof: &'a [u8],
+}
+
+impl<'a> ProofEnvelope<'a> {
+    /// Split an envelope into public inputs and proof without copying.
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, &'static str> {
+        if bytes.len() < ENVELOPE_HEADER_BYTES + PROOF_BYTES {
+            return Err("envelope too short");
+        }
+        let (header, body) = bytes.split_at(ENVELOPE_HEADER_BYTES);
+        let mut count = [0u8; ENVELOPE_HEADER_BYTES];
+        count.copy_from_slice(header);
+        let total_fields = u32::from_be_bytes(count) as usize;
+        if body.len() % 32 != 0 || body.len() / 32 != total_fields {
+            return Err("envelope field count mismatch");
+        }
+        let (public_inputs, proof) = body.split_at(body.len() - PROOF_BYTES);
+        Ok(Self {
+            public_inputs,
+            proof,
+        })
+    }
+
+    /// Number of 32-byte public inputs carried by the envelope.
+    pub fn num_public_inputs(&self) -> usize {
+        self.public_inputs.len() / 32
+    }
+}
+
+/// Pack public inputs and proof into the canonical envelope read by
+/// [`ProofEnvelope::parse`]. The field count is a big-endian `u32`.
+pub fn pack_envelope(public_inputs: &[u8], proof: &[u8]) -> Result<Vec<u8>, &'static str> {
+    if public_inputs.len() % 32 != 0 {
+        return Err("public inputs must be 32-byte aligned");
+    }
+    if proof.len() != PROOF_BYTES {
+        return Err("proof bytes len");
+    }
+    let total_fields = public_inputs.len() / 32 + PROOF_FIELDS;
+    let total_fields = u32::try_from(total_fields).map_err(|_| "too many public inputs")?;
+    let mut out = Vec::with_capacity(ENVELOPE_HEADER_BYTES + public_inputs.len() + proof.len());
+    out.extend_from_slice(&total_fields.to_be_bytes());
+    out.extend_from_slice(public_inputs);
+    out.extend_from_slice(proof);
+    Ok(out)
+}
+
+/// Compress runs of zero bytes: each run becomes `0x00` followed by its
+/// length (1..=255), every other byte is copied. Proof limbs leave most
+/// high bytes zero, so this alone shrinks an envelope considerably.
+pub fn zero_rle_compress(data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(data.len());
+    let mut i = 0;
+    while i < data.len() {
+        if data[i] != 0 {
+            out.push(data[i]);
+            i += 1;
+            continue;
+        }
+        let run = data[i..]
+            .iter()
+            .take(u8::MAX as usize)
+            .take_while(|&&b| b == 0)
+            .count();
+        out.push(0);
+        out.push(run as u8);
+        i += run;
+    }
+    out
+}
+
+/// Inverse of [`zero_rle_compress`]. Fails rather than produce more than
+/// `max_len` bytes, so a small blob cannot expand without bound.
+pub fn zero_rle_decompress(data: &[u8], max_len: usize) -> Result<Vec<u8>, &'static str> {
+    let mut out = Vec::with_capacity(max_len.min(data.len().saturating_mul(u8::MAX as usize)));
+    let mut bytes = data.iter();
+    while let Some(&b) = bytes.next() {
+        let run = if b == 0 {
+            match bytes.next() {
+                Some(0) => return Err("zero run of length 0"),
+                Some(&n) => n as usize,
+                None => return Err("truncated zero run"),
+            }
+        } else {
+            1
+        };
+        if out.len() + run > max_len {
+            return Err("decompressed data too long");
+        }
+        out.resize(out.len() + run, b);
+    }
+    Ok(out)
+}
+
+/// How a named proof component differs between two proofs.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DiffKind {
+    /// The single commitment of this component changed.
+    Point,
+    /// These indices of an array component changed.
+    Entries(Vec<usize>),
+}
+
+/// Components of `a` and `b` that differ, by [`Proof::components`] name and
+/// in wire order. Meant for triaging a prover upgrade, not for verification.
+#[cfg(feature = "std")]
+pub fn diff_proofs(a: &Proof, b: &Proof) -> Vec<(&'static str, DiffKind)> {
+    use crate::types::ProofComponent::{Points, Scalars};
+
+    fn changed<T: PartialEq>(x: &[T], y: &[T]) -> Vec<usize> {
+        x.iter()
+            .zip(y)
+            .enumerate()
+            .filter(|(_, (l, r))| l != r)
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    let mut out = Vec::new();
+    for ((name, x), (_, y)) in a.components().into_iter().zip(b.components()) {
+        let (indices, single) = match (x, y) {
+            (Scalars(x), Scalars(y)) => (changed(x, y), false),
+            (Points(x), Points(y)) => (changed(x, y), x.len() == 1),
+            _ => unreachable!("both proofs list the same components"),
+        };
+        if indices.is_empty() {
+            continue;
+        }
+        out.push((
+            name,
+            if single {
+                DiffKind::Point
+            } else {
+                DiffKind::Entries(indices)
+            },
+        ));
+    }
+    out
+}
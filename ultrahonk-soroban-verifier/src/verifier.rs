@@ -1,33 +1,177 @@
 //! UltraHonk verifier
 
 use crate::{
+    debug::{bytes_to_hex, challenge_label, fr_to_hex, DivergenceReport, ReferenceTranscript},
+    ec::{
+        g1_msm, host_curve_available, pairing_check, pairing_terms, PairingTerms, LHS_G2_BYTES,
+        RHS_G2_BYTES,
+    },
     field::Fr,
-    shplemini::verify_shplemini,
-    sumcheck::verify_sumcheck,
-    transcript::generate_transcript,
-    types::PAIRING_POINTS_SIZE,
+    hash::hash32,
+    shplemini::{shplemini_pairing_points, verify_shplemini, ShpleminiError},
+    sumcheck::{check_final_claim, sumcheck_final_claim, verify_sumcheck},
+    transcript::{challenge_digest, generate_transcript, generate_transcript_with},
+    types::{
+        BbVersion, G1Point, LagrangeWarning, Proof, PublicInputLayout, Transcript,
+        CONST_PROOF_SIZE_LOG_N, NUMBER_OF_ENTITIES, NUMBER_OF_SUBRELATIONS,
+    },
     utils::{load_proof, load_vk_from_bytes},
+    PROOF_BYTES,
+};
+use soroban_sdk::{crypto::bn254::Bn254G1Affine, Bytes, Env};
+
+#[cfg(not(feature = "std"))]
+use alloc::{collections::BTreeMap, format, string::String, vec::Vec};
+#[cfg(feature = "std")]
+use std::{
+    collections::BTreeMap,
+    time::{Duration, Instant},
 };
-use soroban_sdk::{Bytes, Env};
 
 /// Error type describing the specific reason verification failed.
 #[derive(Debug)]
 pub enum VerifyError {
     InvalidInput(&'static str),
+    /// The public inputs feed every challenge, so passing the wrong ones for
+    /// a valid proof surfaces here (past round 0), indistinguishable from a
+    /// forged proof. Check the inputs and their order first.
     SumcheckFailed(&'static str),
-    ShplonkFailed(&'static str),
+    /// Match on the [`ShpleminiError`] to tell a malformed proof from one
+    /// that fails the pairing.
+    ShplonkFailed(ShpleminiError),
+}
+
+impl VerifyError {
+    /// Stable numeric code for the arm, independent of the detail string.
+    pub fn code(&self) -> u32 {
+        match self {
+            VerifyError::InvalidInput(_) => 1,
+            VerifyError::SumcheckFailed(_) => 2,
+            VerifyError::ShplonkFailed(_) => 3,
+        }
+    }
+
+    /// Stable identifier for the arm, for reporting across languages.
+    pub fn machine_message(&self) -> &'static str {
+        match self {
+            VerifyError::InvalidInput(_) => "INVALID_INPUT",
+            VerifyError::SumcheckFailed(_) => "SUMCHECK_FAILED",
+            VerifyError::ShplonkFailed(_) => "SHPLONK_FAILED",
+        }
+    }
+}
+
+/// What [`UltraHonkVerifier::verify_steps`] found at each stage. A stage
+/// left at `None` was not reached; `result` names the failure.
+#[derive(Debug)]
+pub struct VerifySteps {
+    /// The Fiat–Shamir challenges, once the proof and public inputs parsed.
+    pub transcript: Option<Transcript>,
+    /// Sum-check's final `(grand_relation, target)`, once every round check
+    /// passed; sum-check passed iff the two are equal.
+    pub sumcheck: Option<(Fr, Fr)>,
+    /// The Shplemini opening and pairing, once sum-check passed.
+    pub shplemini: Option<Result<(), ShpleminiError>>,
+    /// What [`verify`](UltraHonkVerifier::verify) returns.
+    pub result: Result<(), VerifyError>,
+}
+
+/// Verification stages reported by `check_up_to_pairing_with`.
+#[derive(Clone, Copy)]
+enum Stage {
+    /// Proof and public input checks.
+    Parse,
+    /// Fiat–Shamir challenges and the public input delta.
+    Transcript,
+    Sumcheck,
+}
+
+/// Wall-clock time spent in each stage of [`UltraHonkVerifier::verify_timed`].
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct VerifyTimings {
+    pub parse: Duration,
+    pub transcript: Duration,
+    pub sumcheck: Duration,
+    /// Shplemini batching down to the two pairing inputs (the MSM).
+    pub shplemini: Duration,
+    pub pairing: Duration,
+}
+
+/// Every protocol parameter a verifier uses, so two deployments can be
+/// compared for equality; [`to_bytes`](Self::to_bytes) gives a canonical
+/// encoding to hash or store. The VK is not included.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VerifierManifest {
+    pub version: BbVersion,
+    pub const_proof_size_log_n: u32,
+    pub number_of_entities: u32,
+    pub number_of_subrelations: u32,
+    pub pairing_points_size: u32,
+    pub transcript_hash: &'static str,
+    pub limb_split_bits: u32,
+    /// `[1]₁`, which [`UltraHonkVerifier::with_generator`] can override.
+    pub generator: G1Point,
+    pub rhs_g2: [u8; 128],
+    pub lhs_g2: [u8; 128],
+}
+
+impl VerifierManifest {
+    /// The five counts and `limb_split_bits` as big-endian `u32`s in field
+    /// order, `transcript_hash` as a length byte and ASCII, then the
+    /// generator (64 bytes) and both G2 points (128 bytes each). `version`
+    /// is implied by the rest.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(5 * 4 + 1 + self.transcript_hash.len() + 64 + 256);
+        for word in [
+            self.const_proof_size_log_n,
+            self.number_of_entities,
+            self.number_of_subrelations,
+            self.pairing_points_size,
+            self.limb_split_bits,
+        ] {
+            out.extend_from_slice(&word.to_be_bytes());
+        }
+        out.push(self.transcript_hash.len() as u8);
+        out.extend_from_slice(self.transcript_hash.as_bytes());
+        out.extend_from_slice(&self.generator.to_bytes());
+        out.extend_from_slice(&self.rhs_g2);
+        out.extend_from_slice(&self.lhs_g2);
+        out
+    }
 }
 
 pub struct UltraHonkVerifier {
     env: Env,
     vk: crate::types::VerificationKey,
+    /// Base point for the Shplemini constant term; the SRS `[1]₁`.
+    generator: G1Point,
+    /// bb release whose transcript and proof layout are expected.
+    version: BbVersion,
+    /// Public input index and the value it must hold, e.g. a chain id.
+    expected_domain: Option<(usize, Fr)>,
 }
 
 impl UltraHonkVerifier {
+    /// Build from an in-memory VK, e.g. one assembled with
+    /// [`VerificationKey::from_points`](crate::types::VerificationKey::from_points)
+    /// instead of parsed from bb's bytes.
     pub fn new_with_vk(env: &Env, vk: crate::types::VerificationKey) -> Self {
+        Self::new_with_vk_and_version(env, vk, BbVersion::default())
+    }
+
+    /// Build from an in-memory VK for proofs produced by a specific bb release.
+    pub fn new_with_vk_and_version(
+        env: &Env,
+        vk: crate::types::VerificationKey,
+        version: BbVersion,
+    ) -> Self {
         Self {
             env: env.clone(),
             vk,
+            generator: G1Point::generator(),
+            version,
+            expected_domain: None,
         }
     }
 
@@ -37,19 +181,376 @@ impl UltraHonkVerifier {
             .ok_or(VerifyError::InvalidInput("vk parse error"))
     }
 
+    /// Override the G1 generator used for the Shplemini constant term.
+    /// Only needed for a custom SRS; defaults to the BN254 generator (1, 2).
+    pub fn with_generator(mut self, generator: G1Point) -> Self {
+        self.generator = generator;
+        self
+    }
+
+    /// Require public input `index` to be `value`, so a proof bound to
+    /// another chain or deployment is rejected before any hashing.
+    pub fn with_expected_domain(mut self, index: usize, value: Fr) -> Self {
+        self.expected_domain = Some((index, value));
+        self
+    }
+
     /// Expose a reference to the parsed VK for debugging/inspection.
     pub fn get_vk(&self) -> &crate::types::VerificationKey {
         &self.vk
     }
 
-    /// Top-level verify
+    /// Non-fatal check of the VK's lagrange commitments; see
+    /// [`VerificationKey::lagrange_sanity`](crate::types::VerificationKey::lagrange_sanity).
+    pub fn lagrange_sanity(&self) -> Option<LagrangeWarning> {
+        self.vk.lagrange_sanity(&self.generator)
+    }
+
+    /// The G1 generator the Shplemini constant term is committed against.
+    pub fn get_generator(&self) -> &G1Point {
+        &self.generator
+    }
+
+    /// The bb release this verifier expects proofs from.
+    pub fn get_version(&self) -> BbVersion {
+        self.version
+    }
+
+    /// The protocol parameters this verifier checks proofs against.
+    pub fn manifest(&self) -> VerifierManifest {
+        VerifierManifest {
+            version: self.version,
+            const_proof_size_log_n: CONST_PROOF_SIZE_LOG_N as u32,
+            number_of_entities: NUMBER_OF_ENTITIES as u32,
+            number_of_subrelations: NUMBER_OF_SUBRELATIONS as u32,
+            pairing_points_size: self.version.pairing_points_size() as u32,
+            transcript_hash: self.version.transcript_hash(),
+            limb_split_bits: self.version.limb_split_bits(),
+            generator: self.generator,
+            rhs_g2: RHS_G2_BYTES,
+            lhs_g2: LHS_G2_BYTES,
+        }
+    }
+
+    /// Top-level verify. Same checks as [`verify_steps`](Self::verify_steps),
+    /// but keeps no report, so the transcript is never cloned.
     pub fn verify(
         &self,
         proof_bytes: &Bytes,
         public_inputs_bytes: &Bytes,
     ) -> Result<(), VerifyError> {
-        // 1) parse proof
-        let proof = load_proof(proof_bytes);
+        let (proof, t) = self.check_up_to_pairing(proof_bytes, public_inputs_bytes)?;
+
+        // 6) Shplonk
+        verify_shplemini(&self.env, &proof, &self.vk, &t, &self.generator)
+            .map_err(VerifyError::ShplonkFailed)
+    }
+
+    /// Verify, keeping what each stage produced, so a failure shows whether
+    /// the transcript, sum-check or Shplemini went wrong without `trace`.
+    pub fn verify_steps(&self, proof_bytes: &Bytes, public_inputs_bytes: &Bytes) -> VerifySteps {
+        let mut steps = VerifySteps {
+            transcript: None,
+            sumcheck: None,
+            shplemini: None,
+            result: Ok(()),
+        };
+        steps.result = self.run_steps(proof_bytes, public_inputs_bytes, &mut steps);
+        steps
+    }
+
+    fn run_steps(
+        &self,
+        proof_bytes: &Bytes,
+        public_inputs_bytes: &Bytes,
+        steps: &mut VerifySteps,
+    ) -> Result<(), VerifyError> {
+        let (proof, t) =
+            self.parse_and_transcript(proof_bytes, public_inputs_bytes, &mut |_| {})?;
+        steps.transcript = Some(t.clone());
+
+        let (grand, target) =
+            sumcheck_final_claim(&proof, &t, &self.vk).map_err(VerifyError::SumcheckFailed)?;
+        steps.sumcheck = Some((grand, target));
+        check_final_claim(grand, target).map_err(VerifyError::SumcheckFailed)?;
+
+        // 6) Shplonk, over the same `proof` sum-check read its evaluations from
+        let opened = verify_shplemini(&self.env, &proof, &self.vk, &t, &self.generator);
+        steps.shplemini = Some(opened);
+        opened.map_err(VerifyError::ShplonkFailed)
+    }
+
+    /// [`verify`](Self::verify), returning the
+    /// [`challenge_digest`](crate::transcript::challenge_digest) of the
+    /// accepted proof's transcript.
+    pub fn verify_and_commit_transcript(
+        &self,
+        proof_bytes: &Bytes,
+        public_inputs_bytes: &Bytes,
+    ) -> Result<[u8; 32], VerifyError> {
+        let (proof, t) = self.check_up_to_pairing(proof_bytes, public_inputs_bytes)?;
+        verify_shplemini(&self.env, &proof, &self.vk, &t, &self.generator)
+            .map_err(VerifyError::ShplonkFailed)?;
+        Ok(challenge_digest(&self.env, &t))
+    }
+
+    /// Verify `proofs[i]` against `public_inputs[i]` for every `i` with a
+    /// single pairing. Parsing, transcript, sum-check and the Shplemini MSM
+    /// still run per proof; the pairing inputs are then folded as
+    /// `Σ ρ^i·P0_i` and `Σ ρ^i·P1_i`.
+    ///
+    /// Sound only while `ρ` is unpredictable to the prover: it is a hash over
+    /// every proof's challenge digest and pairing points, so changing any proof
+    /// changes `ρ`, and a bad proof survives only if the others cancel it for a
+    /// `ρ` fixed after they were chosen. A failure does not say which proof is
+    /// bad; see [`verify_each`](Self::verify_each).
+    pub fn verify_batch(
+        &self,
+        proofs: &[Bytes],
+        public_inputs: &[Bytes],
+    ) -> Result<(), VerifyError> {
+        if proofs.len() != public_inputs.len() {
+            return Err(VerifyError::InvalidInput("batch length mismatch"));
+        }
+        if proofs.is_empty() {
+            return Err(VerifyError::InvalidInput("empty batch"));
+        }
+        let mut p0s = Vec::with_capacity(proofs.len());
+        let mut p1s = Vec::with_capacity(proofs.len());
+        let mut seed = Bytes::new(&self.env);
+        for (proof_bytes, public_inputs_bytes) in proofs.iter().zip(public_inputs) {
+            let (proof, t) = self.check_up_to_pairing(proof_bytes, public_inputs_bytes)?;
+            let (p0, p1) =
+                shplemini_pairing_points(&self.env, &proof, &self.vk, &t, &self.generator)
+                    .map_err(VerifyError::ShplonkFailed)?;
+            seed.extend_from_slice(&challenge_digest(&self.env, &t));
+            seed.extend_from_slice(&p0.to_array());
+            seed.extend_from_slice(&p1.to_array());
+            p0s.push(G1Point::from_bytes(p0.to_array()));
+            p1s.push(G1Point::from_bytes(p1.to_array()));
+        }
+        let rho = Fr::from_bytes(&hash32(&seed));
+        let mut powers = Vec::with_capacity(proofs.len());
+        let mut pow = Fr::one();
+        for _ in 0..proofs.len() {
+            powers.push(pow);
+            pow *= rho;
+        }
+        let layout = |_| VerifyError::ShplonkFailed(ShpleminiError::LayoutMismatch);
+        let p0 = g1_msm(&self.env, &p0s, &powers).map_err(layout)?;
+        let p1 = g1_msm(&self.env, &p1s, &powers).map_err(layout)?;
+        if pairing_check(&self.env, &p0, &p1) {
+            Ok(())
+        } else {
+            Err(VerifyError::ShplonkFailed(ShpleminiError::PairingFailed))
+        }
+    }
+
+    /// Fallback for a failed [`verify_batch`](Self::verify_batch): verify
+    /// each pair on its own and report the index of the first that fails.
+    pub fn verify_each(
+        &self,
+        proofs: &[Bytes],
+        public_inputs: &[Bytes],
+    ) -> Result<(), (usize, VerifyError)> {
+        if proofs.len() != public_inputs.len() {
+            let shorter = proofs.len().min(public_inputs.len());
+            return Err((shorter, VerifyError::InvalidInput("batch length mismatch")));
+        }
+        for (i, (proof_bytes, public_inputs_bytes)) in proofs.iter().zip(public_inputs).enumerate()
+        {
+            self.verify(proof_bytes, public_inputs_bytes)
+                .map_err(|e| (i, e))?;
+        }
+        Ok(())
+    }
+
+    /// [`verify`](Self::verify), also reporting the wall-clock time of each
+    /// stage. Stages after a failure keep a zero duration.
+    #[cfg(feature = "std")]
+    pub fn verify_timed(
+        &self,
+        proof_bytes: &Bytes,
+        public_inputs_bytes: &Bytes,
+    ) -> (Result<(), VerifyError>, VerifyTimings) {
+        let mut timings = VerifyTimings::default();
+        let mut last = Instant::now();
+        let mut lap = || {
+            let now = Instant::now();
+            let elapsed = now - last;
+            last = now;
+            elapsed
+        };
+        let result = self
+            .check_up_to_pairing_with(proof_bytes, public_inputs_bytes, &mut |stage| {
+                let elapsed = lap();
+                match stage {
+                    Stage::Parse => timings.parse = elapsed,
+                    Stage::Transcript => timings.transcript = elapsed,
+                    Stage::Sumcheck => timings.sumcheck = elapsed,
+                }
+            })
+            .and_then(|(proof, t)| {
+                let points =
+                    shplemini_pairing_points(&self.env, &proof, &self.vk, &t, &self.generator)
+                        .map_err(VerifyError::ShplonkFailed);
+                timings.shplemini = lap();
+                let (p0, p1) = points?;
+                let paired = pairing_check(&self.env, &p0, &p1);
+                timings.pairing = lap();
+                if paired {
+                    Ok(())
+                } else {
+                    Err(VerifyError::ShplonkFailed(ShpleminiError::PairingFailed))
+                }
+            });
+        (result, timings)
+    }
+
+    /// Verify with public inputs given by name, ordered by `layout` rather
+    /// than by however the caller assembled them.
+    pub fn verify_named(
+        &self,
+        proof_bytes: &Bytes,
+        inputs: &BTreeMap<String, Fr>,
+        layout: &PublicInputLayout,
+    ) -> Result<(), VerifyError> {
+        let public_inputs = layout.assemble(inputs).map_err(VerifyError::InvalidInput)?;
+        self.verify(proof_bytes, &Bytes::from_slice(&self.env, &public_inputs))
+    }
+
+    /// Run every check except the final pairing and return its inputs,
+    /// `[(P0, rhs_g2), (P1, lhs_g2)]`, for an external multi-pairing.
+    ///
+    /// `P1` is already negated (`-kzg_quotient`), so the proof is valid iff
+    /// `e(P0, rhs_g2) · e(P1, lhs_g2) == 1`; callers batching with other
+    /// systems must not negate it again.
+    pub fn verify_pairing_terms(
+        &self,
+        proof_bytes: &Bytes,
+        public_inputs_bytes: &Bytes,
+    ) -> Result<PairingTerms, VerifyError> {
+        let (p0, p1) = self.verify_up_to_pairing(proof_bytes, public_inputs_bytes)?;
+        Ok(pairing_terms(&self.env, &p0, &p1))
+    }
+
+    /// Run every check except the final pairing and return the G1 points
+    /// `(P0, P1)` of [`verify_pairing_terms`](Self::verify_pairing_terms),
+    /// for a pairing done elsewhere (e.g. by an off-chain oracle).
+    pub fn verify_up_to_pairing(
+        &self,
+        proof_bytes: &Bytes,
+        public_inputs_bytes: &Bytes,
+    ) -> Result<(Bn254G1Affine, Bn254G1Affine), VerifyError> {
+        let (proof, t) = self.check_up_to_pairing(proof_bytes, public_inputs_bytes)?;
+        shplemini_pairing_points(&self.env, &proof, &self.vk, &t, &self.generator)
+            .map_err(VerifyError::ShplonkFailed)
+    }
+
+    /// Derive the Fiat–Shamir challenges and compare them, in derivation
+    /// order, against `reference` (e.g. a transcript dumped by bb).
+    ///
+    /// Stops at the first mismatch and reports the challenge, both values and
+    /// the bytes absorbed for that round, which usually shows whether an
+    /// encoding difference is to blame. No sum-check or pairing is run.
+    pub fn verify_against_reference(
+        &self,
+        proof_bytes: &Bytes,
+        public_inputs_bytes: &Bytes,
+        reference: &ReferenceTranscript,
+    ) -> Result<(), DivergenceReport> {
+        let proof = match load_proof(proof_bytes) {
+            Ok(proof) => proof,
+            Err(_) => {
+                return Err(DivergenceReport {
+                    challenge_name: String::from("proof length"),
+                    rust_value: format!("{} bytes", proof_bytes.len()),
+                    reference_value: format!("{PROOF_BYTES} bytes"),
+                    absorbed_bytes_hex: String::new(),
+                })
+            }
+        };
+        let pis_total =
+            (public_inputs_bytes.len() / 32) as u64 + self.version.pairing_points_size() as u64;
+        let mut divergence = None;
+        generate_transcript_with(
+            &self.env,
+            &proof,
+            public_inputs_bytes,
+            self.vk.circuit_size,
+            pis_total,
+            self.vk.pub_inputs_offset,
+            &mut |name, index, value, absorbed| {
+                if divergence.is_some() {
+                    return;
+                }
+                let label = challenge_label(name, index);
+                match reference.get(&label) {
+                    Some(expected) if *expected != value => {
+                        let absorbed: Vec<u8> = absorbed.iter().collect();
+                        divergence = Some(DivergenceReport {
+                            challenge_name: label,
+                            rust_value: fr_to_hex(&value),
+                            reference_value: fr_to_hex(expected),
+                            absorbed_bytes_hex: bytes_to_hex(&absorbed),
+                        });
+                    }
+                    _ => {}
+                }
+            },
+        );
+        divergence.map_or(Ok(()), Err)
+    }
+
+    /// Parse, build the transcript and run sum-check.
+    fn check_up_to_pairing(
+        &self,
+        proof_bytes: &Bytes,
+        public_inputs_bytes: &Bytes,
+    ) -> Result<(Proof, Transcript), VerifyError> {
+        self.check_up_to_pairing_with(proof_bytes, public_inputs_bytes, &mut |_| {})
+    }
+
+    /// [`check_up_to_pairing`](Self::check_up_to_pairing), calling
+    /// `stage_done` as each stage completes.
+    fn check_up_to_pairing_with(
+        &self,
+        proof_bytes: &Bytes,
+        public_inputs_bytes: &Bytes,
+        stage_done: &mut impl FnMut(Stage),
+    ) -> Result<(Proof, Transcript), VerifyError> {
+        let (proof, t) = self.parse_and_transcript(proof_bytes, public_inputs_bytes, stage_done)?;
+
+        // 5) Sum-check
+        verify_sumcheck(&proof, &t, &self.vk).map_err(VerifyError::SumcheckFailed)?;
+        stage_done(Stage::Sumcheck);
+
+        Ok((proof, t))
+    }
+
+    /// Parse and check the inputs, then derive the transcript and public
+    /// input delta.
+    fn parse_and_transcript(
+        &self,
+        proof_bytes: &Bytes,
+        public_inputs_bytes: &Bytes,
+        stage_done: &mut impl FnMut(Stage),
+    ) -> Result<(Proof, Transcript), VerifyError> {
+        // 0) fail cleanly where the BN254 host functions would trap
+        if !host_curve_available(&self.env) {
+            return Err(VerifyError::InvalidInput(
+                "protocol lacks the bn254 host functions",
+            ));
+        }
+
+        // 1) parse proof; sum-check and Shplemini rely on this size check
+        let proof = load_proof(proof_bytes).map_err(VerifyError::InvalidInput)?;
+        proof
+            .validate_dimensions(self.vk.log_circuit_size)
+            .map_err(VerifyError::InvalidInput)?;
+        proof
+            .validate_coordinates()
+            .map_err(VerifyError::InvalidInput)?;
 
         // 2) sanity on public inputs (length and VK metadata if present)
         if public_inputs_bytes.len() % 32 != 0 {
@@ -58,18 +559,37 @@ impl UltraHonkVerifier {
             ));
         }
         let provided = (public_inputs_bytes.len() / 32) as u64;
+        // The VK counts the pairing point object among its public inputs; a
+        // smaller total means it was built for a different layout.
         let expected = self
             .vk
             .public_inputs_size
-            .checked_sub(PAIRING_POINTS_SIZE as u64)
-            .ok_or(VerifyError::InvalidInput("vk inputs < 16"))?;
+            .checked_sub(self.version.pairing_points_size() as u64)
+            .ok_or(VerifyError::InvalidInput(
+                "vk public_inputs_size inconsistent with pairing point size",
+            ))?;
         if expected != provided {
             return Err(VerifyError::InvalidInput("public inputs mismatch"));
         }
+        if let Some((index, value)) = self.expected_domain {
+            // Compare the encoding itself; parsing into Fr would reduce it.
+            let mut word = [0u8; 32];
+            if (index as u64) < provided {
+                let start = index as u32 * 32;
+                public_inputs_bytes
+                    .slice(start..start + 32)
+                    .copy_into_slice(&mut word);
+            }
+            if (index as u64) >= provided || word != value.to_bytes() {
+                return Err(VerifyError::InvalidInput("domain separator mismatch"));
+            }
+        }
+
+        stage_done(Stage::Parse);
 
         // 3) Fiat–Shamir transcript
-        let pis_total = provided + PAIRING_POINTS_SIZE as u64;
-        let pub_inputs_offset = 1;
+        let pis_total = provided + self.version.pairing_points_size() as u64;
+        let pub_inputs_offset = self.vk.pub_inputs_offset;
         let mut t = generate_transcript(
             &self.env,
             &proof,
@@ -90,22 +610,73 @@ impl UltraHonkVerifier {
         )
         .map_err(VerifyError::InvalidInput)?;
 
-        // 5) Sum-check
-        verify_sumcheck(&proof, &t, &self.vk).map_err(VerifyError::SumcheckFailed)?;
+        stage_done(Stage::Transcript);
 
-        // 6) Shplonk
-        verify_shplemini(&self.env, &proof, &self.vk, &t).map_err(VerifyError::ShplonkFailed)?;
-
-        Ok(())
+        Ok((proof, t))
     }
 
-    fn compute_public_input_delta(
+    /// Permutation-argument public input delta over the 32-byte big-endian
+    /// `public_inputs` followed by `pairing_point_object`.
+    pub fn compute_public_input_delta(
         public_inputs: &Bytes,
         pairing_point_object: &[Fr],
         beta: Fr,
         gamma: Fr,
         offset: u64,
         n: u64,
+    ) -> Result<Fr, &'static str> {
+        let parsed = (0..public_inputs.len() / 32).map(|i| {
+            let mut arr = [0u8; 32];
+            public_inputs
+                .slice(i * 32..(i + 1) * 32)
+                .copy_into_slice(&mut arr);
+            Fr::from_bytes(&arr)
+        });
+        Self::compute_public_input_delta_fields(
+            parsed.chain(pairing_point_object.iter().copied()),
+            beta,
+            gamma,
+            offset,
+            n,
+        )
+    }
+
+    /// [`compute_public_input_delta`](Self::compute_public_input_delta) over a
+    /// plain byte slice, e.g. public inputs and a `beta`/`gamma` captured from
+    /// a bb run, with no `Env` needed. Unlike the `Bytes` form, a length that
+    /// is not a multiple of 32 is an error rather than truncated.
+    pub fn compute_public_input_delta_slice(
+        public_inputs: &[u8],
+        pairing_point_object: &[Fr],
+        beta: Fr,
+        gamma: Fr,
+        offset: u64,
+        n: u64,
+    ) -> Result<Fr, &'static str> {
+        if public_inputs.len() % 32 != 0 {
+            return Err("public inputs are not 32-byte words");
+        }
+        let parsed = public_inputs
+            .chunks_exact(32)
+            .map(|word| Fr::from_bytes(word.try_into().unwrap()));
+        Self::compute_public_input_delta_fields(
+            parsed.chain(pairing_point_object.iter().copied()),
+            beta,
+            gamma,
+            offset,
+            n,
+        )
+    }
+
+    /// [`compute_public_input_delta`](Self::compute_public_input_delta) over
+    /// already parsed field elements, pairing point object included, so
+    /// callers holding `Fr`s need not re-encode them.
+    pub fn compute_public_input_delta_fields<I: IntoIterator<Item = Fr>>(
+        public_inputs: I,
+        beta: Fr,
+        gamma: Fr,
+        offset: u64,
+        n: u64,
     ) -> Result<Fr, &'static str> {
         let mut numerator = Fr::one();
         let mut denominator = Fr::one();
@@ -113,22 +684,11 @@ impl UltraHonkVerifier {
         let mut numerator_acc = gamma + beta * Fr::from_u64(n + offset);
         let mut denominator_acc = gamma - beta * Fr::from_u64(offset + 1);
 
-        let mut idx = 0u32;
-        while idx < public_inputs.len() {
-            let mut arr = [0u8; 32];
-            public_inputs.slice(idx..idx + 32).copy_into_slice(&mut arr);
-            let public_input = Fr::from_bytes(&arr);
-            numerator = numerator * (numerator_acc + public_input);
-            denominator = denominator * (denominator_acc + public_input);
-            numerator_acc = numerator_acc + beta;
-            denominator_acc = denominator_acc - beta;
-            idx += 32;
-        }
-        for public_input in pairing_point_object {
-            numerator = numerator * (numerator_acc + *public_input);
-            denominator = denominator * (denominator_acc + *public_input);
-            numerator_acc = numerator_acc + beta;
-            denominator_acc = denominator_acc - beta;
+        for public_input in public_inputs {
+            numerator *= numerator_acc + public_input;
+            denominator *= denominator_acc + public_input;
+            numerator_acc += beta;
+            denominator_acc -= beta;
         }
         let denominator_inv = denominator
             .inverse()
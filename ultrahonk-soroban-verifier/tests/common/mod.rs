@@ -0,0 +1,47 @@
+//! Fixtures shared by the integration tests.
+#![allow(dead_code)]
+
+use soroban_sdk::{Bytes, Env};
+use ultrahonk_soroban_verifier::{
+    transcript::generate_transcript,
+    types::{G1Point, Proof, Transcript, VerificationKey},
+    utils::{load_proof_from_slice, load_vk_from_slice, VK_BYTES},
+    PROOF_BYTES,
+};
+
+/// A VK with every commitment at the identity: a 2^`log_n`-gate circuit
+/// with `public_inputs_size` public inputs (16 of them the pairing point
+/// object) and bb's `pub_inputs_offset` of 1.
+pub fn zero_vk_bytes(log_n: u8, public_inputs_size: u8) -> [u8; VK_BYTES] {
+    let mut raw = [0u8; VK_BYTES];
+    raw[..8].copy_from_slice(&(1u64 << log_n).to_be_bytes()); // circuit_size
+    raw[15] = log_n; // log_circuit_size
+    raw[23] = public_inputs_size; // public_inputs_size
+    raw[31] = 1; // pub_inputs_offset
+    raw
+}
+
+/// [`zero_vk_bytes`], parsed.
+pub fn zero_vk(log_n: u8, public_inputs_size: u8) -> VerificationKey {
+    load_vk_from_slice(&zero_vk_bytes(log_n, public_inputs_size)).unwrap()
+}
+
+/// A 32-byte big-endian value from 64 hex digits.
+pub fn hex32(s: &str) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    for (i, byte) in out.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&s[2 * i..2 * i + 2], 16).unwrap();
+    }
+    out
+}
+
+/// Zeroed proof on [`zero_vk`] with genuine quotient points, so each
+/// Shplemini check can be tripped on its own.
+pub fn opening_fixture(env: &Env, log_n: u8) -> (VerificationKey, Proof, Transcript) {
+    let vk = zero_vk(log_n, 16);
+    let mut proof = load_proof_from_slice(&[0u8; PROOF_BYTES]).unwrap();
+    let t = generate_transcript(env, &proof, &Bytes::new(env), 1 << 4, 16, 1);
+    proof.shplonk_q = G1Point::generator();
+    proof.kzg_quotient = G1Point::generator();
+    (vk, proof, t)
+}
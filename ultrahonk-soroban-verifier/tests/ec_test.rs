@@ -0,0 +1,67 @@
+mod common;
+
+use common::zero_vk_bytes;
+use soroban_sdk::{testutils::Ledger, Bytes, Env};
+use ultrahonk_soroban_verifier::{
+    ec::{self, g1_msm, pairing_check},
+    field::Fr,
+    hash,
+    types::G1Point,
+    verifier::VerifyError,
+    UltraHonkVerifier, PROOF_BYTES,
+};
+
+fn env() -> Env {
+    let env = Env::default();
+    env.ledger().set_protocol_version(25);
+    env
+}
+
+#[test]
+fn empty_msm_is_the_identity() {
+    let env = env();
+    let acc = g1_msm(&env, &[], &[]).unwrap();
+    assert_eq!(acc.to_array(), G1Point::infinity().to_bytes());
+}
+
+#[test]
+fn cancelling_msm_returns_the_identity_cleanly() {
+    let env = env();
+    let g = G1Point::generator();
+    let minus_one = Fr::zero() - Fr::one();
+    let three = Fr::from_u64(3);
+    let acc = g1_msm(&env, &[g, g, g], &[three, minus_one, minus_one - Fr::one()]).unwrap();
+    assert_eq!(acc.to_array(), G1Point::infinity().to_bytes());
+
+    // The identity feeds straight into the pairing: e(O, ·)·e(O, ·) == 1.
+    assert!(pairing_check(&env, &acc, &acc));
+}
+
+#[test]
+fn msm_rejects_length_mismatch() {
+    let env = env();
+    assert!(g1_msm(&env, &[G1Point::generator()], &[]).is_err());
+}
+
+#[test]
+fn backends_are_the_soroban_host() {
+    assert_eq!(ec::active_backend_name(), "soroban-precompile");
+    assert_eq!(hash::active_backend_name(), "soroban-precompile");
+}
+
+#[test]
+fn old_protocol_is_rejected_before_any_host_curve_call() {
+    let env = Env::default();
+    env.ledger()
+        .set_protocol_version(ec::MIN_PROTOCOL_VERSION - 1);
+    let vk = Bytes::from_slice(&env, &zero_vk_bytes(4, 17));
+    let verifier = UltraHonkVerifier::new(&env, &vk).unwrap();
+    let proof = Bytes::from_slice(&env, &[0u8; PROOF_BYTES]);
+    let public_inputs = Bytes::from_slice(&env, &[0u8; 32]);
+    assert!(matches!(
+        verifier.verify(&proof, &public_inputs),
+        Err(VerifyError::InvalidInput(
+            "protocol lacks the bn254 host functions"
+        ))
+    ));
+}
@@ -0,0 +1,96 @@
+use ultrahonk_soroban_verifier::field::Fr;
+
+#[test]
+fn zero_has_no_inverse() {
+    assert_eq!(Fr::zero().inverse(), None);
+}
+
+#[test]
+fn inverse_round_trips() {
+    for x in [1u64, 2, 3, 0xdead_beef, u64::MAX] {
+        let x = Fr::from_u64(x);
+        assert_eq!(x * x.inverse().unwrap(), Fr::one());
+    }
+    let minus_one = Fr::zero() - Fr::one();
+    assert_eq!(minus_one.inverse(), Some(minus_one));
+}
+
+#[test]
+fn batch_inverse_matches_single_inversions() {
+    let mut values: Vec<Fr> = (1..=20u64).map(|x| Fr::from_u64(x * 0x1234_5678)).collect();
+    let expected: Vec<Fr> = values.iter().map(|v| v.inverse().unwrap()).collect();
+    assert!(Fr::batch_inverse(&mut values));
+    assert_eq!(values, expected);
+
+    assert!(Fr::batch_inverse(&mut []));
+}
+
+#[test]
+fn batch_inverse_rejects_a_zero_and_leaves_input_untouched() {
+    let mut values = [Fr::from_u64(3), Fr::zero(), Fr::from_u64(5)];
+    let before = values;
+    assert!(!Fr::batch_inverse(&mut values));
+    assert_eq!(values, before);
+}
+
+#[test]
+fn from_str_accepts_prefixes_and_odd_lengths() {
+    assert_eq!(Fr::from_str("0x1"), Fr::one());
+    assert_eq!(Fr::from_str("abc"), Fr::from_u64(0xabc));
+    assert_eq!(Fr::from_str("0xABC"), Fr::from_u64(0xabc));
+    let full = "0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000";
+    assert_eq!(Fr::from_str(full), Fr::zero() - Fr::one());
+}
+
+#[test]
+fn assign_operators_match_the_binary_ones() {
+    let minus_one = Fr::zero() - Fr::one();
+    for (x, y) in [
+        (Fr::from_u64(7), Fr::from_u64(0xdead_beef)),
+        (minus_one, Fr::from_u64(2)),
+        (Fr::zero(), minus_one),
+    ] {
+        let mut z = x;
+        z += y;
+        assert_eq!(z, x + y);
+        let mut z = x;
+        z -= y;
+        assert_eq!(z, x - y);
+        let mut z = x;
+        z *= y;
+        assert_eq!(z, x * y);
+    }
+}
+
+#[test]
+fn sum_and_product_fold_from_the_identities() {
+    let xs: Vec<Fr> = (1..=5).map(Fr::from_u64).collect();
+    assert_eq!(xs.iter().sum::<Fr>(), Fr::from_u64(15));
+    assert_eq!(xs.iter().copied().product::<Fr>(), Fr::from_u64(120));
+    assert_eq!(core::iter::empty::<Fr>().sum::<Fr>(), Fr::zero());
+    assert_eq!(core::iter::empty::<Fr>().product::<Fr>(), Fr::one());
+
+    // Wraps modulo r like the binary operators.
+    let minus_one = Fr::zero() - Fr::one();
+    assert_eq!([minus_one, Fr::one()].iter().sum::<Fr>(), Fr::zero());
+    assert_eq!([minus_one, minus_one].iter().product::<Fr>(), Fr::one());
+}
+
+#[test]
+fn canonical_decoding_rejects_r_and_above() {
+    let be = |hex: &str| <[u8; 32]>::try_from(hex::decode(hex).unwrap()).unwrap();
+    let r = be("30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001");
+    let r_minus_one = be("30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000");
+    let r_plus_one = be("30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000002");
+
+    assert_eq!(Fr::from_bytes_canonical(&r), None);
+    assert_eq!(Fr::from_bytes_canonical(&r_plus_one), None);
+    assert_eq!(Fr::from_bytes_canonical(&[0xff; 32]), None);
+    assert_eq!(
+        Fr::from_bytes_canonical(&r_minus_one),
+        Some(Fr::zero() - Fr::one())
+    );
+    assert_eq!(Fr::from_bytes_canonical(&[0; 32]), Some(Fr::zero()));
+    // The reducing decoder maps r + 1 onto 1; the canonical one refuses.
+    assert_eq!(Fr::from_bytes(&r_plus_one), Fr::one());
+}
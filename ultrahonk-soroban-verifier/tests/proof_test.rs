@@ -0,0 +1,236 @@
+mod common;
+
+use common::{hex32, zero_vk_bytes};
+use soroban_sdk::{Bytes, Env};
+use ultrahonk_soroban_verifier::{
+    field::Fr,
+    types::{G1Point, CONST_PROOF_SIZE_LOG_N},
+    utils::load_proof,
+    verifier::VerifyError,
+    UltraHonkVerifier, PROOF_BYTES,
+};
+
+/// Split a small coordinate into bb's four 68-bit limbs.
+fn limbs(coord: u128) -> [Fr; 4] {
+    let mask = (1u128 << 68) - 1;
+    [
+        Fr::from_bytes(&be32(coord & mask)),
+        Fr::from_bytes(&be32(coord >> 68)),
+        Fr::zero(),
+        Fr::zero(),
+    ]
+}
+
+fn be32(x: u128) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    out[16..].copy_from_slice(&x.to_be_bytes());
+    out
+}
+
+fn proof_with(object: [Fr; 16]) -> ultrahonk_soroban_verifier::types::Proof {
+    let env = Env::default();
+    let mut proof = load_proof(&Bytes::from_slice(&env, &[0u8; PROOF_BYTES])).unwrap();
+    proof.pairing_point_object = object;
+    proof
+}
+
+fn object(p0: (u128, u128), p1: (u128, u128)) -> [Fr; 16] {
+    let mut out = [Fr::zero(); 16];
+    for (i, c) in [p0.0, p0.1, p1.0, p1.1].into_iter().enumerate() {
+        out[4 * i..4 * i + 4].copy_from_slice(&limbs(c));
+    }
+    out
+}
+
+#[test]
+fn pairing_points_decode_the_generator_and_identity() {
+    let proof = proof_with(object((1, 2), (0, 0)));
+    assert_eq!(
+        proof.pairing_points(),
+        Ok((G1Point::generator(), G1Point::infinity()))
+    );
+}
+
+#[test]
+fn pairing_points_reject_malformed_limbs() {
+    let proof = proof_with(object((1, 3), (1, 2)));
+    assert_eq!(
+        proof.pairing_points(),
+        Err("pairing point is not on the curve")
+    );
+
+    // A coordinate spanning limbs must be reassembled, not truncated.
+    let wide = proof_with(object((1 << 70, 2), (1, 2)));
+    assert_eq!(
+        wide.pairing_points(),
+        Err("pairing point is not on the curve")
+    );
+
+    let mut object = object((1, 2), (1, 2));
+    object[0] = Fr::from_bytes(&be32(1 << 68));
+    assert_eq!(
+        proof_with(object).pairing_points(),
+        Err("pairing point limb exceeds 68 bits")
+    );
+}
+
+/// bb's limb split of an arbitrary 32-byte big-endian coordinate.
+fn limbs_of(coord: [u8; 32]) -> [Fr; 4] {
+    let bit = |i: usize| (coord[31 - i / 8] >> (i % 8)) & 1;
+    core::array::from_fn(|l| {
+        let mut limb = 0u128;
+        for i in (68 * l..(68 * l + 68).min(256)).rev() {
+            limb = (limb << 1) | bit(i) as u128;
+        }
+        Fr::from_bytes(&be32(limb))
+    })
+}
+
+#[test]
+fn pairing_points_reassemble_full_width_coordinates() {
+    let two_g = G1Point::from_xy(
+        hex32("030644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd3"),
+        hex32("15ed738c0e0a7c92e7845f96b2ae9c0a68a6a449e3538fc7ff3ebf7a5a18a2c4"),
+    );
+    let mut object = [Fr::zero(); 16];
+    object[..4].copy_from_slice(&limbs_of(two_g.x));
+    object[4..8].copy_from_slice(&limbs_of(two_g.y));
+    object[8..12].copy_from_slice(&limbs_of(G1Point::generator().x));
+    object[12..].copy_from_slice(&limbs_of(G1Point::generator().y));
+    assert_eq!(
+        proof_with(object).pairing_points(),
+        Ok((two_g, G1Point::generator()))
+    );
+}
+
+#[test]
+fn dimensions_must_cover_log_n() {
+    let proof = proof_with([Fr::zero(); 16]);
+    assert_eq!(proof.validate_dimensions(1), Ok(()));
+    assert_eq!(
+        proof.validate_dimensions(CONST_PROOF_SIZE_LOG_N as u64),
+        Ok(())
+    );
+    assert_eq!(
+        proof.validate_dimensions(0),
+        Err("log_n must be at least 1")
+    );
+    assert_eq!(
+        proof.validate_dimensions(CONST_PROOF_SIZE_LOG_N as u64 + 1),
+        Err("too few sumcheck univariates for log_n")
+    );
+}
+
+#[test]
+fn oversized_vk_is_rejected_instead_of_panicking() {
+    let env = Env::default();
+    let vk = zero_vk_bytes(29, 16);
+    let verifier = UltraHonkVerifier::new(&env, &Bytes::from_slice(&env, &vk)).unwrap();
+    let proof = Bytes::from_slice(&env, &[0u8; PROOF_BYTES]);
+    assert!(matches!(
+        verifier.verify(&proof, &Bytes::new(&env)),
+        Err(VerifyError::InvalidInput(
+            "too few sumcheck univariates for log_n"
+        ))
+    ));
+}
+
+#[test]
+fn zero_log_vk_is_rejected_instead_of_panicking() {
+    // A single-gate circuit: Gemini would have no fold rounds and Shplemini
+    // would index an empty list of powers of r.
+    let env = Env::default();
+    let vk = zero_vk_bytes(0, 16);
+    let verifier = UltraHonkVerifier::new(&env, &Bytes::from_slice(&env, &vk)).unwrap();
+    let proof = Bytes::from_slice(&env, &[0u8; PROOF_BYTES]);
+    assert!(matches!(
+        verifier.verify(&proof, &Bytes::new(&env)),
+        Err(VerifyError::InvalidInput("log_n must be at least 1"))
+    ));
+}
+
+#[test]
+fn vk_without_room_for_pairing_points_is_rejected() {
+    let env = Env::default();
+    // 10 public inputs, short of the 16 pairing point limbs
+    let vk = zero_vk_bytes(4, 10);
+    let verifier = UltraHonkVerifier::new(&env, &Bytes::from_slice(&env, &vk)).unwrap();
+    let proof = Bytes::from_slice(&env, &[0u8; PROOF_BYTES]);
+    assert!(matches!(
+        verifier.verify(&proof, &Bytes::new(&env)),
+        Err(VerifyError::InvalidInput(
+            "vk public_inputs_size inconsistent with pairing point size"
+        ))
+    ));
+}
+
+const BN254_FQ_MODULUS: &str = "30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd47";
+
+#[test]
+fn non_canonical_commitment_coordinates_are_rejected() {
+    let env = Env::default();
+    let vk = zero_vk_bytes(4, 16);
+    let verifier = UltraHonkVerifier::new(&env, &Bytes::from_slice(&env, &vk)).unwrap();
+
+    // w1.x = p, split into the 136-bit low and 118-bit high limbs at words 16 and 17.
+    let p = hex32(BN254_FQ_MODULUS);
+    let mut raw = [0u8; PROOF_BYTES];
+    raw[16 * 32 + 15..17 * 32].copy_from_slice(&p[15..]);
+    raw[17 * 32 + 17..18 * 32].copy_from_slice(&p[..15]);
+    let proof = Bytes::from_slice(&env, &raw);
+    assert_eq!(load_proof(&proof).unwrap().w1.x, p);
+    assert!(matches!(
+        verifier.verify(&proof, &Bytes::new(&env)),
+        Err(VerifyError::InvalidInput(
+            "proof coordinate is not below the base field modulus"
+        ))
+    ));
+
+    let mut below = p;
+    below[31] -= 1;
+    let mut proof = load_proof(&proof).unwrap();
+    proof.w1 = G1Point::from_xy(below, [0u8; 32]);
+    assert_eq!(proof.validate_coordinates(), Ok(()));
+    proof.kzg_quotient = G1Point::from_xy([0u8; 32], p);
+    assert!(proof.validate_coordinates().is_err());
+}
+
+#[test]
+fn wrong_domain_separator_is_rejected_before_the_transcript() {
+    let env = Env::default();
+    let vk = Bytes::from_slice(&env, &zero_vk_bytes(4, 18));
+    let proof = Bytes::from_slice(&env, &[0u8; PROOF_BYTES]);
+    // [commitment, chain_id = 7]
+    let mut inputs = [0u8; 64];
+    inputs[63] = 7;
+    let inputs = Bytes::from_slice(&env, &inputs);
+
+    let cost_of = |verifier: UltraHonkVerifier| {
+        env.cost_estimate().budget().reset_unlimited();
+        let result = verifier.verify(&proof, &inputs);
+        (result, env.cost_estimate().budget().cpu_instruction_cost())
+    };
+    let bound_to = |index, chain_id| {
+        UltraHonkVerifier::new(&env, &vk)
+            .unwrap()
+            .with_expected_domain(index, Fr::from_u64(chain_id))
+    };
+
+    let (other_chain, cheap) = cost_of(bound_to(1, 8));
+    assert!(matches!(
+        other_chain,
+        Err(VerifyError::InvalidInput("domain separator mismatch"))
+    ));
+    assert!(matches!(
+        cost_of(bound_to(2, 7)).0,
+        Err(VerifyError::InvalidInput("domain separator mismatch"))
+    ));
+
+    // The right chain id gets past the input checks; the zero proof fails later.
+    let (same_chain, full) = cost_of(bound_to(1, 7));
+    assert!(matches!(
+        same_chain,
+        Err(VerifyError::SumcheckFailed(_) | VerifyError::ShplonkFailed(_))
+    ));
+    assert!(cheap * 10 < full, "{cheap} vs {full}");
+}
@@ -0,0 +1,322 @@
+//! Property tests for individual subrelations.
+//!
+//! At the sumcheck challenge point the subrelations of an honest proof do not
+//! vanish individually (only their alpha-batched sum matches the final target),
+//! so these tests build single hypercube rows that satisfy a relation and
+//! check that exactly the expected output indices are zero.
+
+use ultrahonk_soroban_verifier::{
+    field::Fr,
+    relations::{
+        accumulate_relation_evaluations, check_lookup_only, check_permutation_only,
+        evaluate_subrelations, grouped_contributions, RELATION_GROUPS, SUBRELATION_NAMES,
+    },
+    types::{RelationParameters, Wire, NUMBER_OF_ENTITIES, NUMBER_OF_SUBRELATIONS},
+};
+
+struct Lcg(u64);
+
+impl Lcg {
+    fn fr(&mut self) -> Fr {
+        let mut bytes = [0u8; 32];
+        for chunk in bytes.chunks_mut(8) {
+            self.0 = self
+                .0
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            chunk.copy_from_slice(&self.0.to_be_bytes());
+        }
+        Fr::from_bytes(&bytes)
+    }
+}
+
+fn random_row(rng: &mut Lcg) -> [Fr; NUMBER_OF_ENTITIES] {
+    core::array::from_fn(|_| rng.fr())
+}
+
+fn random_params(rng: &mut Lcg) -> RelationParameters {
+    RelationParameters {
+        eta: rng.fr(),
+        eta_two: rng.fr(),
+        eta_three: rng.fr(),
+        beta: rng.fr(),
+        gamma: rng.fr(),
+        public_inputs_delta: rng.fr(),
+    }
+}
+
+/// Switch off every gate selector so only the relations under test are active.
+fn disable_selectors(row: &mut [Fr; NUMBER_OF_ENTITIES]) {
+    for w in [
+        Wire::QArith,
+        Wire::QRange,
+        Wire::QElliptic,
+        Wire::QAux,
+        Wire::QLookup,
+        Wire::QPoseidon2External,
+        Wire::QPoseidon2Internal,
+    ] {
+        row[w.index()] = Fr::zero();
+    }
+}
+
+#[test]
+fn permutation_vanishes_on_a_valid_z_perm_row() {
+    let mut rng = Lcg(0x5eed);
+    for _ in 0..16 {
+        let mut row = random_row(&mut rng);
+        let rp = random_params(&mut rng);
+        // Identity permutation: numerator and denominator terms coincide, so a
+        // constant grand product (z_perm_shift == z_perm) satisfies the argument.
+        for (sigma, id) in [
+            (Wire::Sigma1, Wire::Id1),
+            (Wire::Sigma2, Wire::Id2),
+            (Wire::Sigma3, Wire::Id3),
+            (Wire::Sigma4, Wire::Id4),
+        ] {
+            row[sigma.index()] = row[id.index()];
+        }
+        row[Wire::ZPermShift.index()] = row[Wire::ZPerm.index()];
+        row[Wire::LagrangeFirst.index()] = Fr::zero();
+        row[Wire::LagrangeLast.index()] = Fr::zero();
+
+        let out = evaluate_subrelations(&row, &rp, rng.fr());
+        assert_eq!(out[2], Fr::zero(), "permutation grand product");
+        assert_eq!(out[3], Fr::zero(), "permutation last-row");
+
+        // Breaking the copy constraint must be visible in subrelation 2 only.
+        row[Wire::ZPermShift.index()] = row[Wire::ZPerm.index()] + Fr::one();
+        let out = evaluate_subrelations(&row, &rp, Fr::one());
+        assert_ne!(out[2], Fr::zero());
+        assert_eq!(out[3], Fr::zero());
+    }
+}
+
+#[test]
+fn check_permutation_only_matches_the_full_evaluation() {
+    let mut rng = Lcg(0x9e57);
+    for _ in 0..16 {
+        let row = random_row(&mut rng);
+        let rp = random_params(&mut rng);
+        let pow = rng.fr();
+        let out = evaluate_subrelations(&row, &rp, pow);
+        assert_eq!(check_permutation_only(&row, &rp, pow), (out[2], out[3]));
+    }
+}
+
+#[test]
+fn lookup_vanishes_on_a_row_reading_its_own_table_entry() {
+    let mut rng = Lcg(0x100c);
+    for _ in 0..16 {
+        let mut row = random_row(&mut rng);
+        let rp = random_params(&mut rng);
+        let w = |row: &[Fr; NUMBER_OF_ENTITIES], wire: Wire| row[wire.index()];
+        let read_term = w(&row, Wire::Wl)
+            + rp.gamma
+            + w(&row, Wire::Qr) * w(&row, Wire::WlShift)
+            + (w(&row, Wire::Wr) + w(&row, Wire::Qm) * w(&row, Wire::WrShift)) * rp.eta
+            + (w(&row, Wire::Wo) + w(&row, Wire::Qc) * w(&row, Wire::WoShift)) * rp.eta_two
+            + w(&row, Wire::Qo) * rp.eta_three;
+        // Pick table column 1 so the row's table entry is exactly what it reads.
+        row[Wire::Table1.index()] = read_term
+            - rp.gamma
+            - w(&row, Wire::Table2) * rp.eta
+            - w(&row, Wire::Table3) * rp.eta_two
+            - w(&row, Wire::Table4) * rp.eta_three;
+        row[Wire::QLookup.index()] = Fr::one();
+        row[Wire::LookupReadTags.index()] = Fr::one();
+        row[Wire::LookupReadCounts.index()] = Fr::one();
+        row[Wire::LookupInverses.index()] = (read_term * read_term).inverse().unwrap();
+
+        let pow = rng.fr();
+        assert_eq!(check_lookup_only(&row, &rp, pow), (Fr::zero(), Fr::zero()));
+        let out = evaluate_subrelations(&row, &rp, pow);
+        assert_eq!((out[4], out[5]), (Fr::zero(), Fr::zero()));
+
+        // Reading the entry twice without a second table row breaks the sum.
+        row[Wire::LookupReadCounts.index()] = Fr::from_u64(2);
+        let (inverse, accumulate) = check_lookup_only(&row, &rp, pow);
+        assert_eq!(inverse, Fr::zero());
+        assert_ne!(accumulate, Fr::zero());
+    }
+}
+
+#[test]
+fn check_lookup_only_matches_the_full_evaluation() {
+    let mut rng = Lcg(0x10c4);
+    for _ in 0..16 {
+        let row = random_row(&mut rng);
+        let rp = random_params(&mut rng);
+        let pow = rng.fr();
+        let out = evaluate_subrelations(&row, &rp, pow);
+        assert_eq!(check_lookup_only(&row, &rp, pow), (out[4], out[5]));
+    }
+}
+
+#[test]
+fn arithmetic_vanishes_on_a_satisfied_gate() {
+    let mut rng = Lcg(0xa417);
+    for _ in 0..16 {
+        let mut row = random_row(&mut rng);
+        let rp = random_params(&mut rng);
+        disable_selectors(&mut row);
+        row[Wire::QArith.index()] = Fr::one();
+        // With q_arith = 1: q_m·w_l·w_r + q_l·w_l + q_r·w_r + q_o·w_o + q_4·w_4 + q_c = 0.
+        let w = |x: Wire| row[x.index()];
+        let qc = Fr::zero()
+            - (w(Wire::Qm) * w(Wire::Wl) * w(Wire::Wr)
+                + w(Wire::Ql) * w(Wire::Wl)
+                + w(Wire::Qr) * w(Wire::Wr)
+                + w(Wire::Qo) * w(Wire::Wo)
+                + w(Wire::Q4) * w(Wire::W4));
+        row[Wire::Qc.index()] = qc;
+
+        let out = evaluate_subrelations(&row, &rp, rng.fr());
+        assert_eq!(out[0], Fr::zero(), "arithmetic gate");
+        assert_eq!(out[1], Fr::zero(), "arithmetic mini-addition");
+        for (i, v) in out.iter().enumerate().skip(18) {
+            assert_eq!(
+                *v,
+                Fr::zero(),
+                "poseidon subrelation {i} with selectors off"
+            );
+        }
+
+        row[Wire::Qc.index()] = qc + Fr::one();
+        let out = evaluate_subrelations(&row, &rp, Fr::one());
+        assert_ne!(out[0], Fr::zero());
+    }
+}
+
+#[test]
+fn batching_the_subrelations_matches_the_grand_relation() {
+    let mut rng = Lcg(0xba7c);
+    let row = random_row(&mut rng);
+    let rp = random_params(&mut rng);
+    let alphas: Vec<Fr> = (1..NUMBER_OF_SUBRELATIONS).map(|_| rng.fr()).collect();
+    let pow = rng.fr();
+
+    let out = evaluate_subrelations(&row, &rp, pow);
+    let mut batched = out[0];
+    for i in 1..NUMBER_OF_SUBRELATIONS {
+        batched += out[i] * alphas[i - 1];
+    }
+    assert_eq!(
+        batched,
+        accumulate_relation_evaluations(&row, &rp, &alphas, pow)
+    );
+}
+
+#[test]
+fn relation_groups_sum_to_the_grand_relation() {
+    let mut rng = Lcg(0x9a0c);
+    let row = random_row(&mut rng);
+    let rp = random_params(&mut rng);
+    let alphas: Vec<Fr> = (1..NUMBER_OF_SUBRELATIONS).map(|_| rng.fr()).collect();
+    let pow = rng.fr();
+
+    let groups = grouped_contributions(&row, &rp, &alphas, pow);
+    let total = groups.iter().fold(Fr::zero(), |acc, g| acc + *g);
+    assert_eq!(
+        total,
+        accumulate_relation_evaluations(&row, &rp, &alphas, pow)
+    );
+    // Every subrelation name starts with its family's name.
+    let mut start = 0;
+    for (name, end) in RELATION_GROUPS {
+        for sub in &SUBRELATION_NAMES[start..end] {
+            assert!(sub.starts_with(name), "{sub} outside {name}");
+        }
+        start = end;
+    }
+    assert_eq!(start, NUMBER_OF_SUBRELATIONS);
+}
+
+#[test]
+fn subrelation_names_are_unique() {
+    for (i, a) in SUBRELATION_NAMES.iter().enumerate() {
+        for b in &SUBRELATION_NAMES[i + 1..] {
+            assert_ne!(a, b);
+        }
+    }
+}
+
+/// Each gate selector must only reach the output indices named after its
+/// family. Reordering the `accumulate_*` calls or their `evals[]` slots shifts
+/// which alpha scales each subrelation; this catches that before the fixture
+/// sumcheck does.
+#[test]
+fn selectors_only_reach_their_named_subrelations() {
+    let mut rng = Lcg(0x0da1);
+    for _ in 0..8 {
+        let mut row = random_row(&mut rng);
+        let rp = random_params(&mut rng);
+        disable_selectors(&mut row);
+        let baseline = evaluate_subrelations(&row, &rp, Fr::one());
+        for (i, v) in baseline.iter().enumerate() {
+            let name = SUBRELATION_NAMES[i];
+            if !(name.starts_with("permutation_") || name.starts_with("lookup_")) {
+                assert_eq!(*v, Fr::zero(), "{name} with selectors off");
+            }
+        }
+
+        for (selector, family) in [
+            (Wire::QArith, "arithmetic_"),
+            (Wire::QRange, "delta_range_"),
+            (Wire::QElliptic, "elliptic_"),
+            (Wire::QAux, "auxiliary_"),
+            (Wire::QPoseidon2External, "poseidon2_external_"),
+            (Wire::QPoseidon2Internal, "poseidon2_internal_"),
+        ] {
+            let mut on = row;
+            on[selector.index()] = rng.fr();
+            let out = evaluate_subrelations(&on, &rp, Fr::one());
+            let mut touched = 0;
+            for i in 0..NUMBER_OF_SUBRELATIONS {
+                if out[i] != baseline[i] {
+                    assert!(
+                        SUBRELATION_NAMES[i].starts_with(family),
+                        "{family}* selector reached {}",
+                        SUBRELATION_NAMES[i]
+                    );
+                    touched += 1;
+                }
+            }
+            assert!(touched > 0, "{family}* selector reached nothing");
+        }
+    }
+}
+
+/// Reference form of the Poseidon2 internal round, written out lane by lane
+/// with the bb diagonal, to pin the folded implementation.
+#[test]
+fn poseidon_internal_matches_the_unfolded_formula() {
+    let diag = [
+        Fr::from_str("0x10dc6e9c006ea38b04b1e03b4bd9490c0d03f98929ca1d7fb56821fd19d3b6e7"),
+        Fr::from_str("0x0c28145b6a44df3e0149b3d0a30b3bb599df9756d4dd9b84a86b38cfb45a740b"),
+        Fr::from_str("0x00544b8338791518b2c7645a50392798b21f75bb60e3596170067d00141cac15"),
+        Fr::from_str("0x222c01175718386f2e2e82eb122789e352e105a3b8fa852613bc534433ee428b"),
+    ];
+    let mut rng = Lcg(0x9051);
+    for _ in 0..8 {
+        let row = random_row(&mut rng);
+        let rp = random_params(&mut rng);
+        let pow = rng.fr();
+        let w = |x: Wire| row[x.index()];
+        let u = [
+            (w(Wire::Wl) + w(Wire::Ql)).pow(5),
+            w(Wire::Wr),
+            w(Wire::Wo),
+            w(Wire::W4),
+        ];
+        let u_sum = u[0] + u[1] + u[2] + u[3];
+        let shifts = [Wire::WlShift, Wire::WrShift, Wire::WoShift, Wire::W4Shift];
+
+        let out = evaluate_subrelations(&row, &rp, pow);
+        for i in 0..4 {
+            let expected =
+                (u[i] * diag[i] + u_sum - w(shifts[i])) * w(Wire::QPoseidon2Internal) * pow;
+            assert_eq!(out[22 + i], expected, "poseidon2_internal_{i}");
+        }
+    }
+}
@@ -0,0 +1,169 @@
+mod common;
+
+use common::{opening_fixture, zero_vk};
+use soroban_sdk::{testutils::Ledger, Bytes, Env};
+use ultrahonk_soroban_verifier::{
+    ec::g1_msm,
+    field::Fr,
+    shplemini::{shplemini_pairing_points, verify_shplemini, ShpleminiError},
+    transcript::generate_transcript,
+    types::{G1Point, ShplonkZ},
+    utils::load_proof_from_slice,
+    PROOF_BYTES,
+};
+
+#[test]
+fn identity_quotients_are_rejected() {
+    let env = Env::default();
+    env.ledger().set_protocol_version(25);
+    let vk = zero_vk(4, 16);
+    let raw_proof: Vec<u8> = (0..PROOF_BYTES)
+        .map(|i| if i % 32 < 18 { 0 } else { (i % 251) as u8 })
+        .collect();
+    let mut proof = load_proof_from_slice(&raw_proof).unwrap();
+    let t = generate_transcript(&env, &proof, &Bytes::new(&env), 1 << 4, 16, 1);
+    let g = G1Point::generator();
+
+    proof.shplonk_q = G1Point::infinity();
+    proof.kzg_quotient = g;
+    assert_eq!(
+        verify_shplemini(&env, &proof, &vk, &t, &g),
+        Err(ShpleminiError::ShplonkQIdentity)
+    );
+
+    proof.shplonk_q = g;
+    proof.kzg_quotient = G1Point::infinity();
+    assert_eq!(
+        verify_shplemini(&env, &proof, &vk, &t, &g),
+        Err(ShpleminiError::KzgQuotientIdentity)
+    );
+}
+
+/// Zero VK and proof commitments plus an identity generator leave only
+/// `shplonk_q + z·kzg_quotient` in the MSM; choosing `shplonk_q = -z·G`
+/// cancels it.
+#[test]
+fn identity_p0_is_rejected() {
+    let env = Env::default();
+    env.ledger().set_protocol_version(25);
+    env.cost_estimate().budget().reset_unlimited();
+    let (vk, mut proof, t) = opening_fixture(&env, 4);
+    let g = G1Point::generator();
+
+    let minus_z_g = g1_msm(&env, &[g], &[Fr::zero() - t.shplonk_z.0]).unwrap();
+    proof.shplonk_q = G1Point::from_bytes(minus_z_g.to_array());
+    proof.kzg_quotient = g;
+    let generator = G1Point::infinity();
+
+    assert_eq!(
+        shplemini_pairing_points(&env, &proof, &vk, &t, &generator).err(),
+        Some(ShpleminiError::IdentityP0)
+    );
+    assert_eq!(
+        verify_shplemini(&env, &proof, &vk, &t, &generator),
+        Err(ShpleminiError::IdentityP0)
+    );
+}
+
+#[test]
+fn off_curve_fold_commitments_are_named() {
+    let env = Env::default();
+    env.ledger().set_protocol_version(25);
+    env.cost_estimate().budget().reset_unlimited();
+    let (vk, mut proof, t) = opening_fixture(&env, 4);
+    let g = G1Point::generator();
+
+    let mut one = [0u8; 32];
+    one[31] = 1;
+    let off_curve = G1Point::from_xy(one, one);
+    assert!(!off_curve.is_on_curve());
+    assert!(g.is_on_curve() && G1Point::infinity().is_on_curve());
+
+    // log_n = 4 uses fold commitments 0..3; the rest is padding.
+    proof.gemini_fold_comms[5] = off_curve;
+    assert!(shplemini_pairing_points(&env, &proof, &vk, &t, &g).is_ok());
+
+    proof.gemini_fold_comms[2] = off_curve;
+    assert_eq!(
+        shplemini_pairing_points(&env, &proof, &vk, &t, &g).err(),
+        Some(ShpleminiError::InvalidCommitment { index: 2 })
+    );
+    assert_eq!(
+        ShpleminiError::InvalidCommitment { index: 2 }.message(),
+        "gemini_fold_comms[2] is not on the curve"
+    );
+}
+
+#[test]
+fn each_failure_has_its_own_variant() {
+    let env = Env::default();
+    env.ledger().set_protocol_version(25);
+    env.cost_estimate().budget().reset_unlimited();
+    let g = G1Point::generator();
+
+    // Well-formed but wrong: only the pairing can reject it.
+    let (vk, proof, t) = opening_fixture(&env, 4);
+    assert!(shplemini_pairing_points(&env, &proof, &vk, &t, &g).is_ok());
+    assert_eq!(
+        verify_shplemini(&env, &proof, &vk, &t, &g),
+        Err(ShpleminiError::PairingFailed)
+    );
+
+    // A zero log_n leaves no Gemini rounds to open.
+    let (vk, proof, t) = opening_fixture(&env, 0);
+    assert_eq!(
+        verify_shplemini(&env, &proof, &vk, &t, &g),
+        Err(ShpleminiError::LayoutMismatch)
+    );
+
+    // z = r: the round 0 Shplonk weight divides by zero.
+    let (vk, proof, mut t) = opening_fixture(&env, 4);
+    t.shplonk_z = ShplonkZ(t.gemini_r.0);
+    assert_eq!(
+        verify_shplemini(&env, &proof, &vk, &t, &g),
+        Err(ShpleminiError::DenominatorZero { round: 0 })
+    );
+
+    // z = -r^4: the round 2 negative weight divides by zero.
+    let (vk, proof, mut t) = opening_fixture(&env, 4);
+    let r = t.gemini_r.0;
+    let r4 = r * r * r * r;
+    t.shplonk_z = ShplonkZ(Fr::zero() - r4);
+    assert_eq!(
+        verify_shplemini(&env, &proof, &vk, &t, &g),
+        Err(ShpleminiError::DenominatorZero { round: 2 })
+    );
+
+    // u_1 = r²/(r² - 1): the round 1 fold denominator r²(1 - u) + u vanishes.
+    let (vk, proof, mut t) = opening_fixture(&env, 4);
+    let r2 = t.gemini_r.0 * t.gemini_r.0;
+    t.sumcheck_u_challenges[1] = r2 * (r2 - Fr::one()).inverse().unwrap();
+    assert_eq!(
+        verify_shplemini(&env, &proof, &vk, &t, &g),
+        Err(ShpleminiError::DenominatorZero { round: 1 })
+    );
+}
+
+#[test]
+fn fold_padding_costs_nothing_in_the_msm() {
+    let env = Env::default();
+    env.ledger().set_protocol_version(25);
+    env.cost_estimate().budget().reset_unlimited();
+    let (vk, mut proof, t) = opening_fixture(&env, 4);
+    let g = G1Point::generator();
+    proof.gemini_fold_comms[0] = g;
+
+    let run = |proof: &_| {
+        env.cost_estimate().budget().reset_unlimited();
+        let points = shplemini_pairing_points(&env, proof, &vk, &t, &g).unwrap();
+        (points, env.cost_estimate().budget().cpu_instruction_cost())
+    };
+    let (bare, bare_cost) = run(&proof);
+    // Fill every padding slot (log_n - 1 = 3 onwards) with a real point.
+    for com in &mut proof.gemini_fold_comms[3..] {
+        *com = g;
+    }
+    let (padded, padded_cost) = run(&proof);
+    assert_eq!(padded, bare);
+    assert_eq!(padded_cost, bare_cost);
+}
@@ -0,0 +1,91 @@
+use core::ops::{Add, Mul, Sub};
+use ultrahonk_soroban_verifier::{
+    field::{Fr, ScalarField},
+    sumcheck::{check_sum, compute_next_target_sum, partially_evaluate_pow},
+    types::BATCHED_RELATION_PARTIAL_LENGTH,
+};
+
+const P: u64 = 97;
+
+/// Integers mod 97: large enough for the eight evaluation points to stay
+/// distinct, small enough to check every value by hand.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct F97(u64);
+
+impl Add for F97 {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        F97((self.0 + rhs.0) % P)
+    }
+}
+
+impl Sub for F97 {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        F97((self.0 + P - rhs.0) % P)
+    }
+}
+
+impl Mul for F97 {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        F97((self.0 * rhs.0) % P)
+    }
+}
+
+impl ScalarField for F97 {
+    fn zero() -> Self {
+        F97(0)
+    }
+    fn one() -> Self {
+        F97(1)
+    }
+    fn from_u64(x: u64) -> Self {
+        F97(x % P)
+    }
+    fn inverse(&self) -> Option<Self> {
+        (1..P).find(|y| self.0 * y % P == 1).map(F97)
+    }
+}
+
+/// `X^2 + 1` sampled at `0..8`.
+fn square_plus_one() -> [F97; 8] {
+    [1, 2, 5, 10, 17, 26, 37, 50].map(F97)
+}
+
+#[test]
+fn round_sum_is_checked_over_a_toy_field() {
+    let u = square_plus_one();
+    assert!(check_sum(&u, F97(3)));
+    assert!(!check_sum(&u, F97(4)));
+}
+
+#[test]
+fn next_target_evaluates_the_round_univariate_over_a_toy_field() {
+    let u = square_plus_one();
+    // 10^2 + 1 = 101 = 4 mod 97
+    assert_eq!(compute_next_target_sum(&u, F97(10)).unwrap(), F97(4));
+    // 50^2 + 1 = 2501 = 76 mod 97
+    assert_eq!(compute_next_target_sum(&u, F97(50)).unwrap(), F97(76));
+    // A challenge on an evaluation point has no barycentric form.
+    assert!(compute_next_target_sum(&u, F97(3)).is_err());
+}
+
+#[test]
+fn pow_folds_over_a_toy_field() {
+    // 5 * (1 + 10 * (3 - 1)) = 105 = 8 mod 97
+    assert_eq!(partially_evaluate_pow(F97(3), F97(5), F97(10)), F97(8));
+}
+
+#[test]
+fn bn254_barycentric_table_matches_the_generic_product() {
+    for i in 0..BATCHED_RELATION_PARTIAL_LENGTH {
+        let mut w = Fr::one();
+        for j in 0..BATCHED_RELATION_PARTIAL_LENGTH {
+            if j != i {
+                w *= Fr::from_u64(i as u64) - Fr::from_u64(j as u64);
+            }
+        }
+        assert_eq!(<Fr as ScalarField>::barycentric_weight(i), w, "point {i}");
+    }
+}
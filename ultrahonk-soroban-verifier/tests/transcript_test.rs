@@ -0,0 +1,218 @@
+mod common;
+
+use common::zero_vk_bytes;
+use soroban_sdk::{Bytes, Env};
+use ultrahonk_soroban_verifier::{
+    debug::{challenge_label, fr_to_hex, ReferenceTranscript},
+    field::Fr,
+    transcript::{
+        challenge_digest, generate_transcript, generate_transcript_with, public_inputs_digest,
+        split_challenge,
+    },
+    types::{GeminiR, Rho, ShplonkNu, ShplonkZ},
+    utils::load_proof,
+    UltraHonkVerifier, PROOF_BYTES,
+};
+
+/// Transcript derivation only hashes, so arbitrary bytes are enough here;
+/// the top 18 bytes of each word are cleared so every scalar is below r and
+/// every commitment limb fits its width.
+fn sample(len: usize, seed: u8) -> Vec<u8> {
+    (0..len)
+        .map(|i| match i % 32 {
+            0..18 => 0,
+            _ => (i as u8).wrapping_mul(31).wrapping_add(seed),
+        })
+        .collect()
+}
+
+fn setup(env: &Env) -> (UltraHonkVerifier, Bytes, Bytes) {
+    let vk = Bytes::from_slice(env, &zero_vk_bytes(4, 18));
+    let verifier = UltraHonkVerifier::new(env, &vk).unwrap();
+    let proof = Bytes::from_slice(env, &sample(PROOF_BYTES, 7));
+    let public_inputs = Bytes::from_slice(env, &sample(64, 3));
+    (verifier, proof, public_inputs)
+}
+
+/// Everything the Rust side derives, as a reference would list it.
+fn own_reference(env: &Env, proof: &Bytes, public_inputs: &Bytes) -> ReferenceTranscript {
+    let mut reference = ReferenceTranscript::new();
+    generate_transcript_with(
+        env,
+        &load_proof(proof).unwrap(),
+        public_inputs,
+        1 << 4,
+        2 + 16,
+        1,
+        &mut |name, index, value, _| reference.insert(&challenge_label(name, index), value),
+    );
+    reference
+}
+
+#[test]
+fn matching_reference_reports_no_divergence() {
+    let env = Env::default();
+    let (verifier, proof, public_inputs) = setup(&env);
+    let reference = own_reference(&env, &proof, &public_inputs);
+    assert!(reference.get("sumcheck_u_27").is_some());
+    assert_eq!(
+        verifier.verify_against_reference(&proof, &public_inputs, &reference),
+        Ok(())
+    );
+}
+
+#[test]
+fn wrong_rho_is_named_in_the_report() {
+    let env = Env::default();
+    let (verifier, proof, public_inputs) = setup(&env);
+    let mut reference = own_reference(&env, &proof, &public_inputs);
+    let rust_rho = *reference.get("rho").unwrap();
+    let wrong = rust_rho + Fr::one();
+    reference.insert("rho", wrong);
+
+    let report = verifier
+        .verify_against_reference(&proof, &public_inputs, &reference)
+        .unwrap_err();
+    assert_eq!(report.challenge_name, "rho");
+    assert_eq!(report.rust_value, fr_to_hex(&rust_rho));
+    assert_eq!(report.reference_value, fr_to_hex(&wrong));
+    // rho absorbs the previous challenge and every sumcheck evaluation.
+    assert_eq!(report.absorbed_bytes_hex.len(), 2 * 32 * (1 + 40));
+}
+
+#[test]
+fn reference_parses_trace_output() {
+    let text = "===== TRANSCRIPT PARAMETERS =====\n\
+                eta = 0x0000000000000000000000000000000000000000000000000000000000000005\n\
+                alpha_3 = 0x00000000000000000000000000000000000000000000000000000000000000ff\n\
+                circuit_size = 16\n";
+    let reference = ReferenceTranscript::parse(text).unwrap();
+    assert_eq!(reference.get("eta"), Some(&Fr::from_u64(5)));
+    assert_eq!(reference.get("alpha_3"), Some(&Fr::from_u64(255)));
+    assert_eq!(reference.get("circuit_size"), None);
+
+    let bad = "rho = 0xzz00000000000000000000000000000000000000000000000000000000000000";
+    assert!(ReferenceTranscript::parse(bad).is_err());
+}
+
+#[test]
+fn public_inputs_digest_hashes_the_eta_prefix() {
+    let env = Env::default();
+    let (_, proof, public_inputs) = setup(&env);
+    let mut eta_round = None;
+    generate_transcript_with(
+        &env,
+        &load_proof(&proof).unwrap(),
+        &public_inputs,
+        1 << 4,
+        2 + 16,
+        1,
+        &mut |name, _, _, absorbed| {
+            if name == "eta" {
+                eta_round = Some(absorbed.clone());
+            }
+        },
+    );
+    let eta_round = eta_round.unwrap();
+    let prefix = eta_round.slice(..3 * 32 + public_inputs.len());
+    let expected = env.crypto().keccak256(&prefix).to_array();
+
+    assert_eq!(
+        public_inputs_digest(&env, &public_inputs, 1 << 4, 2 + 16, 1),
+        expected
+    );
+    assert_ne!(
+        public_inputs_digest(&env, &public_inputs, 1 << 5, 2 + 16, 1),
+        expected
+    );
+}
+
+#[test]
+fn typed_challenges_hold_the_named_values() {
+    let env = Env::default();
+    let (_, proof, public_inputs) = setup(&env);
+    let reference = own_reference(&env, &proof, &public_inputs);
+    let t = generate_transcript(
+        &env,
+        &load_proof(&proof).unwrap(),
+        &public_inputs,
+        1 << 4,
+        2 + 16,
+        1,
+    );
+    let named = |label: &str| *reference.get(label).unwrap();
+
+    assert_eq!(t.rho, Rho(named("rho")));
+    assert_eq!(t.gemini_r, GeminiR(named("gemini_r")));
+    assert_eq!(t.shplonk_nu, ShplonkNu(named("shplonk_nu")));
+    assert_eq!(t.shplonk_z, ShplonkZ(named("shplonk_z")));
+}
+
+/// `(challenge, low, high)` as big-endian hex, worked out by hand: `low` is
+/// the last 16 bytes, `high` the first 16, each left-padded with zeros.
+const SPLIT_VECTORS: [(&str, &str, &str); 5] = [
+    ("0", "0", "0"),
+    (
+        "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+        "101112131415161718191a1b1c1d1e1f",
+        "000102030405060708090a0b0c0d0e0f",
+    ),
+    (
+        // 2^128 sits exactly on the boundary.
+        "0000000000000000000000000000000100000000000000000000000000000000",
+        "0",
+        "1",
+    ),
+    (
+        // r - 1, the largest canonical element.
+        "30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000",
+        "2833e84879b9709143e1f593f0000000",
+        "30644e72e131a029b85045b68181585d",
+    ),
+    (
+        // 2^256 - 1 reduced mod r; all-0xFF bytes are not canonical.
+        "0e0a77c19a07df2f666ea36f7879462e36fc76959f60cd29ac96341c4ffffffa",
+        "36fc76959f60cd29ac96341c4ffffffa",
+        "0e0a77c19a07df2f666ea36f7879462e",
+    ),
+];
+
+#[test]
+fn split_challenge_matches_hand_computed_halves() {
+    for (challenge, low, high) in SPLIT_VECTORS {
+        let (l, h) = split_challenge(Fr::from_str(&format!("0x{challenge}")));
+        assert_eq!(
+            l,
+            Fr::from_str(&format!("0x{low}")),
+            "low half of {challenge}"
+        );
+        assert_eq!(
+            h,
+            Fr::from_str(&format!("0x{high}")),
+            "high half of {challenge}"
+        );
+    }
+    assert_eq!(
+        split_challenge(Fr::from_bytes(&[0xff; 32])),
+        split_challenge(Fr::from_str(&format!("0x{}", SPLIT_VECTORS[4].0)))
+    );
+}
+
+#[test]
+fn challenge_digest_is_stable_and_binds_the_public_inputs() {
+    let env = Env::default();
+    let (_, proof, public_inputs) = setup(&env);
+    let proof = load_proof(&proof).unwrap();
+    let digest = |pis: &Bytes| {
+        challenge_digest(
+            &env,
+            &generate_transcript(&env, &proof, pis, 1 << 4, 2 + 16, 1),
+        )
+    };
+    let first = digest(&public_inputs);
+    assert_eq!(digest(&public_inputs), first);
+
+    let mut other = public_inputs.clone();
+    other.set(63, other.get(63).unwrap() ^ 1);
+    assert_ne!(digest(&other), first);
+}
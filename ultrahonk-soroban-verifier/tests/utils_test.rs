@@ -0,0 +1,413 @@
+use soroban_sdk::{Bytes, Env, U256};
+use std::collections::BTreeMap;
+use ultrahonk_soroban_verifier::{
+    field::Fr,
+    types::{ProofComponent, PublicInputLayout},
+    utils::{
+        coord_to_halves_be, fr_from_u256, fr_from_u256_checked, load_proof, load_proof_checked,
+        load_proof_from_slice, load_vk_from_bytes, load_vk_from_slice, pack_envelope, proof_layout,
+        serialize_proof, u256_from_fr, zero_rle_compress, zero_rle_decompress, CheckedProofError,
+        ProofEnvelope, VK_BYTES,
+    },
+    PROOF_BYTES, PROOF_FIELDS,
+};
+
+fn sample(len: usize, seed: u8) -> Vec<u8> {
+    (0..len)
+        .map(|i| (i as u8).wrapping_mul(31) ^ seed)
+        .collect()
+}
+
+/// [`sample`] with the top 18 bytes of each word cleared, so every proof
+/// scalar is below r and every commitment limb fits its width.
+fn proof_sample(len: usize, seed: u8) -> Vec<u8> {
+    let mut bytes = sample(len, seed);
+    for word in bytes.chunks_mut(32) {
+        word[..18].fill(0);
+    }
+    bytes
+}
+
+#[test]
+fn envelope_round_trips() {
+    let public_inputs = sample(3 * 32, 0x5a);
+    let proof = sample(PROOF_BYTES, 0xa5);
+    let packed = pack_envelope(&public_inputs, &proof).unwrap();
+
+    let count = u32::from_be_bytes(packed[..4].try_into().unwrap()) as usize;
+    assert_eq!(count, 3 + PROOF_FIELDS);
+
+    let env = ProofEnvelope::parse(&packed).unwrap();
+    assert_eq!(env.public_inputs, &public_inputs[..]);
+    assert_eq!(env.proof, &proof[..]);
+    assert_eq!(env.num_public_inputs(), 3);
+}
+
+#[test]
+fn envelope_rejects_malformed_inputs() {
+    let proof = sample(PROOF_BYTES, 0);
+    assert!(pack_envelope(&[0u8; 33], &proof).is_err());
+    assert!(pack_envelope(&[0u8; 32], &proof[1..]).is_err());
+
+    let mut packed = pack_envelope(&[0u8; 64], &proof).unwrap();
+    packed[3] ^= 1;
+    assert!(ProofEnvelope::parse(&packed).is_err());
+    assert!(ProofEnvelope::parse(&packed[..PROOF_BYTES]).is_err());
+}
+
+#[test]
+fn wrong_length_proofs_are_errors_not_panics() {
+    let env = Env::default();
+    let raw = proof_sample(PROOF_BYTES + 32, 0x42);
+    for len in [0, 1, 32, PROOF_BYTES - 1, PROOF_BYTES + 1, PROOF_BYTES + 32] {
+        let raw = &raw[..len];
+        assert_eq!(
+            load_proof_from_slice(raw),
+            Err("proof bytes len"),
+            "len {len}"
+        );
+        assert_eq!(
+            load_proof(&Bytes::from_slice(&env, raw)),
+            Err("proof bytes len"),
+            "len {len}"
+        );
+    }
+    assert!(load_proof_from_slice(&raw[..PROOF_BYTES]).is_ok());
+}
+
+#[test]
+fn scalars_at_or_above_r_are_rejected() {
+    let ranges: BTreeMap<_, _> = proof_layout().into_iter().collect();
+    let r =
+        hex::decode("30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001").unwrap();
+    let mut raw = proof_sample(PROOF_BYTES, 0x42);
+    let at = ranges["sumcheck_evaluations"].start;
+    raw[at..at + 32].copy_from_slice(&r);
+    assert_eq!(
+        load_proof_from_slice(&raw),
+        Err("proof scalar is not below the scalar field modulus")
+    );
+    raw[at + 31] -= 1;
+    assert!(load_proof_from_slice(&raw).is_ok());
+}
+
+#[test]
+fn checked_loader_names_the_first_off_curve_commitment() {
+    let ranges: BTreeMap<_, _> = proof_layout().into_iter().collect();
+    // x = y = 1 (the lo limbs), not on y² = x³ + 3.
+    let corrupt = |raw: &mut [u8], field: &str, offset: usize| {
+        let start = ranges[field].start + offset;
+        raw[start + 31] = 1;
+        raw[start + 2 * 32 + 31] = 1;
+    };
+
+    // All-zero commitments are the identity, which is accepted.
+    let mut raw = vec![0u8; PROOF_BYTES];
+    assert!(load_proof_checked(&raw).is_ok());
+
+    corrupt(&mut raw, "gemini_fold_comms", 3 * 128);
+    assert_eq!(
+        load_proof_checked(&raw),
+        Err(CheckedProofError::InvalidPoint {
+            index: 11,
+            field: "gemini_fold_comms"
+        })
+    );
+    corrupt(&mut raw, "w2", 0);
+    assert_eq!(
+        load_proof_checked(&raw),
+        Err(CheckedProofError::InvalidPoint {
+            index: 1,
+            field: "w2"
+        })
+    );
+    // The unchecked loader still accepts it.
+    assert!(load_proof_from_slice(&raw).is_ok());
+
+    assert_eq!(
+        load_proof_checked(&raw[1..]),
+        Err(CheckedProofError::Parse("proof bytes len"))
+    );
+}
+
+#[test]
+fn serialized_proofs_parse_back_to_the_same_proof() {
+    let raw = proof_sample(PROOF_BYTES, 0x6b);
+    let proof = load_proof_from_slice(&raw).unwrap();
+    let bytes = serialize_proof(&proof);
+    assert_eq!(bytes, raw);
+    assert_eq!(load_proof_from_slice(&bytes).unwrap(), proof);
+
+    // Mutating one field changes only that field's bytes.
+    let mut edited = proof.clone();
+    edited.w2 = edited.w3;
+    let edited_bytes = serialize_proof(&edited);
+    let w2 = proof_layout()
+        .into_iter()
+        .find(|(name, _)| *name == "w2")
+        .unwrap()
+        .1;
+    for (i, (a, b)) in bytes.iter().zip(&edited_bytes).enumerate() {
+        if a != b {
+            assert!(w2.contains(&i), "byte {i} changed outside w2");
+        }
+    }
+}
+
+#[test]
+fn fixture_proof_round_trips_through_serialize_proof() -> Result<(), String> {
+    let raw = std::fs::read("circuits/simple_circuit/target/proof").map_err(|e| e.to_string())?;
+    let proof = load_proof_from_slice(&raw)?;
+    assert_eq!(load_proof_from_slice(&serialize_proof(&proof))?, proof);
+    // bb writes canonical limbs, so the fixture comes back byte for byte.
+    assert_eq!(serialize_proof(&proof), raw);
+    Ok(())
+}
+
+#[test]
+fn host_bytes_and_slice_parsers_agree() {
+    let env = Env::default();
+
+    let raw_proof = proof_sample(PROOF_BYTES, 0x17);
+    let proof = Bytes::from_slice(&env, &raw_proof);
+    assert_eq!(
+        load_proof(&proof).unwrap(),
+        load_proof_from_slice(&raw_proof).unwrap()
+    );
+
+    let mut raw_vk = sample(VK_BYTES, 0x3c);
+    // Keep every coordinate below p; the loader rejects anything larger.
+    for word in raw_vk[32..].chunks_mut(32) {
+        word[0] &= 0x0f;
+    }
+    let vk = Bytes::from_slice(&env, &raw_vk);
+    let parsed = load_vk_from_bytes(&vk).expect("vk parses");
+    assert_eq!(Some(parsed), load_vk_from_slice(&raw_vk));
+    assert!(load_vk_from_slice(&raw_vk[1..]).is_none());
+}
+
+#[test]
+fn u256_round_trips_through_fr() {
+    let env = Env::default();
+    // r, the BN254 scalar field order.
+    let r_bytes =
+        hex::decode("30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001").unwrap();
+    let r = U256::from_be_bytes(&env, &Bytes::from_slice(&env, &r_bytes));
+    let one = U256::from_u32(&env, 1);
+
+    let mut below_r = vec![
+        U256::from_u32(&env, 0),
+        one.clone(),
+        U256::from_u128(&env, u128::MAX),
+        r.sub(&one),
+    ];
+    // Walk the field with a large odd stride; x + stride < 2r never overflows.
+    let stride = U256::from_parts(&env, 0x1234_5678_9abc_def0, 0, 0x9e37_79b9, 1);
+    let mut x = one.clone();
+    for _ in 0..32 {
+        x = x.add(&stride).rem_euclid(&r);
+        below_r.push(x.clone());
+    }
+    for x in below_r {
+        let fr = fr_from_u256(&x);
+        assert_eq!(u256_from_fr(&env, &fr), x);
+        assert_eq!(fr_from_u256_checked(&env, &x), Some(fr));
+    }
+
+    // At and above r the value wraps, and the checked variant refuses it.
+    assert_eq!(fr_from_u256(&r), Fr::zero());
+    assert_eq!(fr_from_u256(&r.add(&one)), Fr::one());
+    assert_eq!(fr_from_u256_checked(&env, &r), None);
+    let max = U256::from_parts(&env, u64::MAX, u64::MAX, u64::MAX, u64::MAX);
+    assert_eq!(u256_from_fr(&env, &fr_from_u256(&max)), max.rem_euclid(&r));
+    assert_eq!(fr_from_u256_checked(&env, &max), None);
+}
+
+#[test]
+fn public_input_layout_orders_by_circuit_position() {
+    let layout = PublicInputLayout::new(&["root", "nullifier", "recipient"]).unwrap();
+    assert_eq!(layout.position("recipient"), Some(2));
+    assert_eq!(layout.position("amount"), None);
+
+    // Map iteration is alphabetical; the layout must not follow it.
+    let mut inputs = BTreeMap::new();
+    inputs.insert("recipient".to_string(), Fr::from_u64(3));
+    inputs.insert("nullifier".to_string(), Fr::from_u64(2));
+    inputs.insert("root".to_string(), Fr::from_u64(1));
+    let bytes = layout.assemble(&inputs).unwrap();
+    let expected: Vec<u8> = [1u64, 2, 3]
+        .iter()
+        .flat_map(|x| Fr::from_u64(*x).to_bytes())
+        .collect();
+    assert_eq!(bytes, expected);
+
+    inputs.remove("root");
+    assert!(layout.assemble(&inputs).is_err());
+    inputs.insert("amount".to_string(), Fr::one());
+    assert!(layout.assemble(&inputs).is_err());
+
+    assert_eq!(
+        PublicInputLayout::new(&["root", "root"]),
+        Err("duplicate public input name")
+    );
+}
+
+#[test]
+fn zero_rle_round_trips_and_shrinks_limbs() {
+    // A mostly-zero 32-byte limb pattern, with a run longer than 255.
+    let mut data = vec![0u8; 19 * 32];
+    for word in data.chunks_mut(32) {
+        word[31] = 0x5a;
+        word[30] = 0x01;
+    }
+    data.extend_from_slice(&sample(64, 7));
+    data.extend_from_slice(&[0u8; 300]);
+
+    let packed = zero_rle_compress(&data);
+    assert!(packed.len() < data.len() / 4);
+    assert_eq!(zero_rle_decompress(&packed, data.len()), Ok(data.clone()));
+    assert_eq!(
+        zero_rle_decompress(&packed, data.len() - 1),
+        Err("decompressed data too long")
+    );
+    assert_eq!(zero_rle_decompress(&[1, 0], 8), Err("truncated zero run"));
+    assert_eq!(zero_rle_decompress(&[0, 0], 8), Err("zero run of length 0"));
+}
+
+#[test]
+fn proof_components_cover_every_word_in_order() {
+    let raw_proof = proof_sample(PROOF_BYTES, 0x17);
+    let proof = load_proof_from_slice(&raw_proof).unwrap();
+    let words: usize = proof
+        .components()
+        .iter()
+        .map(|(_, c)| match c {
+            ProofComponent::Scalars(s) => s.len(),
+            ProofComponent::Points(p) => 4 * p.len(),
+        })
+        .sum();
+    assert_eq!(words, PROOF_FIELDS);
+    assert_eq!(proof.components()[0].0, "pairing_point_object");
+    assert_eq!(proof.components()[14].0, "kzg_quotient");
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn diff_proofs_names_only_the_changed_field() {
+    use ultrahonk_soroban_verifier::{
+        types::G1Point,
+        utils::{diff_proofs, DiffKind},
+    };
+
+    let raw_proof = proof_sample(PROOF_BYTES, 0x17);
+    let a = load_proof_from_slice(&raw_proof).unwrap();
+    assert!(diff_proofs(&a, &a.clone()).is_empty());
+
+    let mut b = a.clone();
+    b.gemini_a_evaluations[3] += Fr::one();
+    assert_eq!(
+        diff_proofs(&a, &b),
+        vec![("gemini_a_evaluations", DiffKind::Entries(vec![3]))]
+    );
+
+    b.z_perm = G1Point::generator();
+    assert_eq!(
+        diff_proofs(&a, &b),
+        vec![
+            ("z_perm", DiffKind::Point),
+            ("gemini_a_evaluations", DiffKind::Entries(vec![3])),
+        ]
+    );
+}
+
+#[test]
+fn proof_layout_is_contiguous_and_matches_the_parser() {
+    let layout = proof_layout();
+    assert_eq!(layout.first().unwrap().1.start, 0);
+    assert_eq!(layout.last().unwrap().1.end, PROOF_BYTES);
+    for pair in layout.windows(2) {
+        assert_eq!(
+            pair[0].1.end, pair[1].1.start,
+            "{} / {}",
+            pair[0].0, pair[1].0
+        );
+    }
+    let range = |name: &str| layout.iter().find(|(n, _)| *n == name).unwrap().1.clone();
+    assert_eq!(range("w1"), 16 * 32..20 * 32);
+    assert_eq!(range("sumcheck_univariates").start, 48 * 32);
+    assert_eq!(range("gemini_a_evaluations").start, 420 * 32);
+    assert_eq!(range("kzg_quotient"), (PROOF_FIELDS - 4) * 32..PROOF_BYTES);
+
+    // Bytes inside w4's range change w4 and nothing else.
+    let raw = proof_sample(PROOF_BYTES, 0x17);
+    let mut edited = raw.clone();
+    edited[range("w4").start + 31] ^= 1;
+    assert_ne!(
+        load_proof_from_slice(&raw).unwrap().w4,
+        load_proof_from_slice(&edited).unwrap().w4
+    );
+}
+
+/// `coord_to_halves_be` is the only limb split; pin it to its arithmetic
+/// definition, `(v mod 2^136, v >> 136)`, and to the proof parser's inverse.
+#[test]
+fn coordinate_halves_are_the_136_bit_split() {
+    let env = Env::default();
+    let two_136 = U256::from_u32(&env, 2).pow(136);
+    let mut coords = vec![[0u8; 32], [0xff; 32]];
+    let mut edge = [0u8; 32];
+    edge[14] = 1; // 2^136
+    coords.push(edge);
+    edge[14] = 0;
+    edge[15..].fill(0xff); // 2^136 - 1
+    coords.push(edge);
+    coords.extend((0..16u8).map(|seed| <[u8; 32]>::try_from(sample(32, seed)).unwrap()));
+
+    for coord in coords {
+        let (lo, hi) = coord_to_halves_be(&coord);
+        let v = U256::from_be_bytes(&env, &Bytes::from_array(&env, &coord));
+        let as_u256 = |half: &[u8; 32]| U256::from_be_bytes(&env, &Bytes::from_array(&env, half));
+        assert_eq!(as_u256(&lo), v.rem_euclid(&two_136));
+        assert_eq!(as_u256(&hi), v.shr(136));
+
+        let mut raw = vec![0u8; PROOF_BYTES];
+        let w1 = 16 * 32;
+        raw[w1..w1 + 32].copy_from_slice(&lo);
+        raw[w1 + 32..w1 + 64].copy_from_slice(&hi);
+        let parsed = load_proof_from_slice(&raw);
+        if coord[0] < 0x40 {
+            assert_eq!(parsed.unwrap().w1.x, coord);
+        } else {
+            // At or above 2^254, so never a coordinate below p.
+            assert!(parsed.is_err());
+        }
+    }
+}
+
+#[test]
+fn commitment_limbs_wider_than_136_and_118_bits_are_rejected() {
+    let ranges: BTreeMap<_, _> = proof_layout().into_iter().collect();
+    let w1 = ranges["w1"].start;
+    let raw = proof_sample(PROOF_BYTES, 0x42);
+    assert!(load_proof_from_slice(&raw).is_ok());
+
+    // (byte, value) just past each limb's width: x_lo, x_hi, y_lo, y_hi.
+    for (limb, byte, value) in [
+        (0, 14, 1),
+        (1, 17, 0x40),
+        (1, 16, 1),
+        (2, 0, 1),
+        (3, 17, 0x80),
+    ] {
+        let mut wide = raw.clone();
+        wide[w1 + 32 * limb + byte] = value;
+        assert_eq!(
+            load_proof_from_slice(&wide),
+            Err("proof commitment limb is wider than 136/118 bits")
+        );
+    }
+    // The widest hi limb still fits.
+    let mut widest = raw.clone();
+    widest[w1 + 32 + 17] = 0x3f;
+    assert!(load_proof_from_slice(&widest).is_ok());
+}
@@ -1,24 +1,40 @@
+mod common;
+
+use common::{hex32, zero_vk, zero_vk_bytes};
 use soroban_sdk::{testutils::Ledger, Bytes, Env};
-use std::{fs, path::Path};
-use ultrahonk_soroban_verifier::UltraHonkVerifier;
+use std::{collections::BTreeMap, fs, path::Path};
+use ultrahonk_soroban_verifier::{
+    field::Fr,
+    shplemini::ShpleminiError,
+    transcript::{challenge_digest, generate_transcript},
+    types::{G1Point, PublicInputLayout, VerificationKey},
+    utils::{load_proof, load_proof_from_slice, load_vk_from_slice},
+    verifier::VerifyError,
+    UltraHonkVerifier, PROOF_BYTES,
+};
 
-fn run(dir: &str) -> Result<(), String> {
+/// A verifier over `dir`'s VK, with its proof and public inputs, on a
+/// protocol with the BN254 host functions.
+fn load(env: &Env, dir: &str) -> Result<(UltraHonkVerifier, Bytes, Bytes), String> {
     let path = Path::new(dir);
-    let env = Env::default();
     env.ledger().set_protocol_version(25);
+    let read = |name| {
+        fs::read(path.join(name))
+            .map(|bytes| Bytes::from_slice(env, &bytes))
+            .map_err(|e| e.to_string())
+    };
+    let verifier = UltraHonkVerifier::new(env, &read("vk")?).map_err(|e| format!("{e:?}"))?;
+    Ok((verifier, read("proof")?, read("public_inputs")?))
+}
 
-    // Proof bytes
-    let proof_bytes: Vec<u8> = fs::read(path.join("proof")).map_err(|e| e.to_string())?;
-    let proof = Bytes::from_slice(&env, &proof_bytes);
-
-    // Use binary VK
-    let vk_bytes = fs::read(path.join("vk")).map_err(|e| e.to_string())?;
-    let vk = Bytes::from_slice(&env, &vk_bytes);
-    let verifier = UltraHonkVerifier::new(&env, &vk).map_err(|e| format!("{e:?}"))?;
+/// [`load`] for the simple_circuit fixture.
+fn fixture(env: &Env) -> Result<(UltraHonkVerifier, Bytes, Bytes), String> {
+    load(env, "circuits/simple_circuit/target")
+}
 
-    // Public inputs bytes
-    let public_inputs = fs::read(path.join("public_inputs")).map_err(|e| e.to_string())?;
-    let public_inputs = Bytes::from_slice(&env, &public_inputs);
+fn run(dir: &str) -> Result<(), String> {
+    let env = Env::default();
+    let (verifier, proof, public_inputs) = load(&env, dir)?;
     verifier
         .verify(&proof, &public_inputs)
         .map_err(|e| format!("{e:?}"))?;
@@ -30,7 +46,580 @@ fn simple_circuit_proof_verifies() -> Result<(), String> {
     run("circuits/simple_circuit/target")
 }
 
+#[test]
+fn in_memory_vk_verifies_fixture() -> Result<(), String> {
+    let env = Env::default();
+    let (loaded, proof, public_inputs) = fixture(&env)?;
+    let parsed = loaded.get_vk();
+
+    // Assemble the VK as an embedder would, without going through bytes.
+    let vk = VerificationKey::from_points(
+        parsed.circuit_size,
+        parsed.public_inputs_size,
+        parsed.points(),
+    )?;
+    let verifier = UltraHonkVerifier::new_with_vk(&env, vk);
+    verifier
+        .verify(&proof, &public_inputs)
+        .map_err(|e| format!("{e:?}"))
+}
+
+#[test]
+fn fixture_pairing_points_are_on_the_curve() -> Result<(), String> {
+    let bytes = fs::read("circuits/simple_circuit/target/proof").map_err(|e| e.to_string())?;
+    let proof = load_proof_from_slice(&bytes).unwrap();
+    proof.pairing_points().map(|_| ()).map_err(String::from)
+}
+
+#[test]
+fn named_public_inputs_verify_fixture() -> Result<(), String> {
+    let env = Env::default();
+    let (verifier, proof, pis) = fixture(&env)?;
+    let mut y = [0u8; 32];
+    pis.slice(..32).copy_into_slice(&mut y);
+
+    let mut inputs = BTreeMap::new();
+    inputs.insert("y".to_string(), Fr::from_bytes(&y));
+    let layout = PublicInputLayout::new(&["y"])?;
+    verifier
+        .verify_named(&proof, &inputs, &layout)
+        .map_err(|e| format!("{e:?}"))
+}
+
 #[test]
 fn fib_chain_proof_verifies() -> Result<(), String> {
     run("circuits/fib_chain/target")
 }
+
+fn be_bytes(fq: ark_bn254::Fq) -> [u8; 32] {
+    use ark_ff::{BigInteger, PrimeField};
+    let mut out = [0u8; 32];
+    out.copy_from_slice(&fq.into_bigint().to_bytes_be());
+    out
+}
+
+#[test]
+fn default_generator_matches_arkworks() {
+    use ark_bn254::g1::{G1_GENERATOR_X, G1_GENERATOR_Y};
+    let expected = G1Point::from_xy(be_bytes(G1_GENERATOR_X), be_bytes(G1_GENERATOR_Y));
+    assert_eq!(G1Point::generator(), expected);
+}
+
+#[test]
+fn verify_error_codes_are_stable_and_distinct() {
+    let arms = [
+        (VerifyError::InvalidInput("a"), 1, "INVALID_INPUT"),
+        (VerifyError::SumcheckFailed("b"), 2, "SUMCHECK_FAILED"),
+        (
+            VerifyError::ShplonkFailed(ShpleminiError::PairingFailed),
+            3,
+            "SHPLONK_FAILED",
+        ),
+    ];
+    for (err, code, message) in &arms {
+        assert_eq!(err.code(), *code);
+        assert_eq!(err.machine_message(), *message);
+    }
+    // The detail string never leaks into the stable identifiers.
+    assert_eq!(
+        VerifyError::SumcheckFailed("other").code(),
+        VerifyError::SumcheckFailed("b").code()
+    );
+}
+
+#[test]
+fn generator_override_is_used() -> Result<(), String> {
+    let env = Env::default();
+    let (verifier, proof, public_inputs) = fixture(&env)?;
+    assert_eq!(*verifier.get_generator(), G1Point::generator());
+
+    // 2·G: a valid point, but not the generator the proof was committed against.
+    let two_g = G1Point::from_xy(
+        hex32("030644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd3"),
+        hex32("15ed738c0e0a7c92e7845f96b2ae9c0a68a6a449e3538fc7ff3ebf7a5a18a2c4"),
+    );
+    let verifier = verifier.with_generator(two_g);
+    assert_eq!(*verifier.get_generator(), two_g);
+    match verifier.verify(&proof, &public_inputs) {
+        Err(VerifyError::ShplonkFailed(_)) => Ok(()),
+        other => Err(format!("expected ShplonkFailed, got {other:?}")),
+    }
+}
+
+#[test]
+fn identity_kzg_quotient_is_rejected() -> Result<(), String> {
+    let env = Env::default();
+    let (verifier, mut proof, public_inputs) = fixture(&env)?;
+    // kzg_quotient is the last point: four 32-byte limbs.
+    for i in proof.len() - 4 * 32..proof.len() {
+        proof.set(i, 0);
+    }
+    match verifier.verify(&proof, &public_inputs) {
+        Err(VerifyError::ShplonkFailed(ShpleminiError::KzgQuotientIdentity)) => Ok(()),
+        other => Err(format!("expected identity rejection, got {other:?}")),
+    }
+}
+
+/// Flip the low bit of 32-byte word `word` of `bytes`.
+fn flip_low_bit(bytes: &mut Bytes, word: usize) {
+    let i = (word * 32 + 31) as u32;
+    bytes.set(i, bytes.get_unchecked(i) ^ 1);
+}
+
+/// Which input a negative-path case corrupts.
+enum Target {
+    Proof,
+    PublicInputs,
+}
+
+/// Flip the low bit of 32-byte word `word` of the fixture and return the error.
+fn verify_with_flipped_bit(target: Target, word: usize) -> Result<VerifyError, String> {
+    let env = Env::default();
+    let (verifier, mut proof, mut public_inputs) = fixture(&env)?;
+    let buf = match target {
+        Target::Proof => &mut proof,
+        Target::PublicInputs => &mut public_inputs,
+    };
+    flip_low_bit(buf, word);
+
+    match verifier.verify(&proof, &public_inputs) {
+        Ok(()) => Err(format!("mutated word {word} was accepted")),
+        Err(e) => Ok(e),
+    }
+}
+
+// Word offsets into a bb v0.87 proof: 16 pairing-point limbs, then w1.
+const W1_X_LO: usize = 16;
+const FIRST_SUMCHECK_UNIVARIATE: usize = 48;
+const SECOND_GEMINI_A_EVALUATION: usize = 421;
+
+#[test]
+fn flipped_w1_is_rejected_by_sumcheck() -> Result<(), String> {
+    match verify_with_flipped_bit(Target::Proof, W1_X_LO)? {
+        VerifyError::SumcheckFailed(_) => Ok(()),
+        other => Err(format!("expected SumcheckFailed, got {other:?}")),
+    }
+}
+
+#[test]
+fn flipped_sumcheck_univariate_is_rejected() -> Result<(), String> {
+    match verify_with_flipped_bit(Target::Proof, FIRST_SUMCHECK_UNIVARIATE)? {
+        VerifyError::SumcheckFailed(_) => Ok(()),
+        other => Err(format!("expected SumcheckFailed, got {other:?}")),
+    }
+}
+
+#[test]
+fn tampered_round_zero_univariate_is_reported_as_malformed() -> Result<(), String> {
+    match verify_with_flipped_bit(Target::Proof, FIRST_SUMCHECK_UNIVARIATE + 1)? {
+        VerifyError::SumcheckFailed(
+            "round 0 target must be zero; prover sent nonzero u[0]+u[1]",
+        ) => Ok(()),
+        other => Err(format!("expected the round 0 error, got {other:?}")),
+    }
+}
+
+#[test]
+fn flipped_w1_breaks_a_later_round_not_round_zero() -> Result<(), String> {
+    // The challenges change, but u[0] + u[1] == 0 only depends on the proof.
+    match verify_with_flipped_bit(Target::Proof, W1_X_LO)? {
+        VerifyError::SumcheckFailed(msg) if !msg.starts_with("round 0") => Ok(()),
+        other => Err(format!("expected a later sum-check failure, got {other:?}")),
+    }
+}
+
+#[test]
+fn flipped_gemini_evaluation_is_rejected_by_the_pairing() -> Result<(), String> {
+    // Absorbed only after sum-check, so the round checks still pass.
+    match verify_with_flipped_bit(Target::Proof, SECOND_GEMINI_A_EVALUATION)? {
+        VerifyError::ShplonkFailed(ShpleminiError::PairingFailed) => Ok(()),
+        other => Err(format!("expected ShplonkFailed, got {other:?}")),
+    }
+}
+
+/// `verify_batch` and `verify_each` results for the same inputs.
+type BatchOutcome = (Result<(), VerifyError>, Result<(), (usize, VerifyError)>);
+
+/// Three copies of the fixture; `flip = (bad, word)` flips word `word` of copy `bad`.
+fn verify_fixture_batch(flip: Option<(usize, usize)>) -> Result<BatchOutcome, String> {
+    let env = Env::default();
+    env.cost_estimate().budget().reset_unlimited();
+    let (verifier, proof, pis) = fixture(&env)?;
+    let mut proofs = [proof.clone(), proof.clone(), proof];
+    if let Some((bad, word)) = flip {
+        flip_low_bit(&mut proofs[bad], word);
+    }
+    let public_inputs = [pis.clone(), pis.clone(), pis];
+
+    Ok((
+        verifier.verify_batch(&proofs, &public_inputs),
+        verifier.verify_each(&proofs, &public_inputs),
+    ))
+}
+
+#[test]
+fn batch_of_valid_proofs_verifies() -> Result<(), String> {
+    match verify_fixture_batch(None)? {
+        (Ok(()), Ok(())) => Ok(()),
+        other => Err(format!("expected the batch to verify, got {other:?}")),
+    }
+}
+
+#[test]
+fn one_flipped_proof_fails_the_batch() -> Result<(), String> {
+    for bad in 0..3 {
+        // Passes sum-check, so only the combined pairing can catch it.
+        match verify_fixture_batch(Some((bad, SECOND_GEMINI_A_EVALUATION)))? {
+            (
+                Err(VerifyError::ShplonkFailed(ShpleminiError::PairingFailed)),
+                Err((index, VerifyError::ShplonkFailed(ShpleminiError::PairingFailed))),
+            ) if index == bad => {}
+            other => {
+                return Err(format!(
+                    "proof {bad}: expected a pairing failure, got {other:?}"
+                ))
+            }
+        }
+        match verify_fixture_batch(Some((bad, W1_X_LO)))? {
+            (Err(VerifyError::SumcheckFailed(_)), Err((index, VerifyError::SumcheckFailed(_))))
+                if index == bad => {}
+            other => {
+                return Err(format!(
+                    "proof {bad}: expected SumcheckFailed, got {other:?}"
+                ))
+            }
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn wrong_length_proof_is_invalid_input() {
+    let env = Env::default();
+    env.ledger().set_protocol_version(25);
+    let verifier = UltraHonkVerifier::new_with_vk(&env, zero_vk(4, 16));
+    let public_inputs = Bytes::new(&env);
+    for len in [0, PROOF_BYTES - 32, PROOF_BYTES + 32] {
+        let proof = Bytes::from_slice(&env, &vec![0u8; len]);
+        assert!(matches!(
+            verifier.verify(&proof, &public_inputs),
+            Err(VerifyError::InvalidInput("proof bytes len"))
+        ));
+    }
+}
+
+#[test]
+fn the_vk_public_input_offset_feeds_the_transcript() {
+    let env = Env::default();
+    env.ledger().set_protocol_version(25);
+    let proof = Bytes::from_slice(&env, &vec![0u8; PROOF_BYTES]);
+    let digest = |offset: u8| {
+        let mut raw_vk = zero_vk_bytes(4, 16);
+        raw_vk[31] = offset; // pub_inputs_offset
+        let verifier = UltraHonkVerifier::new_with_vk(&env, load_vk_from_slice(&raw_vk).unwrap());
+        let steps = verifier.verify_steps(&proof, &Bytes::new(&env));
+        challenge_digest(&env, &steps.transcript.unwrap())
+    };
+    assert_ne!(digest(1), digest(2));
+}
+
+#[test]
+fn verify_steps_stops_at_the_failing_stage() {
+    let env = Env::default();
+    env.ledger().set_protocol_version(25);
+    let verifier = UltraHonkVerifier::new_with_vk(&env, zero_vk(4, 16));
+
+    let short = Bytes::from_slice(&env, &[0u8; 32]);
+    let steps = verifier.verify_steps(&short, &Bytes::new(&env));
+    assert!(steps.transcript.is_none() && steps.sumcheck.is_none());
+    assert!(matches!(steps.result, Err(VerifyError::InvalidInput(_))));
+
+    let proof = Bytes::from_slice(&env, &vec![0u8; PROOF_BYTES]);
+    let steps = verifier.verify_steps(&proof, &Bytes::new(&env));
+    assert!(steps.transcript.is_some());
+    let (grand, target) = steps.sumcheck.expect("zero univariates pass every round");
+    match steps.result {
+        Err(VerifyError::SumcheckFailed(_)) => {
+            assert_ne!(grand, target);
+            assert!(steps.shplemini.is_none());
+        }
+        Err(VerifyError::ShplonkFailed(e)) => {
+            assert_eq!(grand, target);
+            assert_eq!(steps.shplemini, Some(Err(e)));
+        }
+        other => panic!("a zero proof must not verify, got {other:?}"),
+    }
+    assert_eq!(
+        format!("{:?}", verifier.verify(&proof, &Bytes::new(&env))),
+        format!(
+            "{:?}",
+            verifier.verify_steps(&proof, &Bytes::new(&env)).result
+        )
+    );
+}
+
+#[test]
+fn fixture_passes_every_step() -> Result<(), String> {
+    let env = Env::default();
+    let (verifier, proof, public_inputs) = fixture(&env)?;
+    let steps = verifier.verify_steps(&proof, &public_inputs);
+    steps.result.map_err(|e| format!("{e:?}"))?;
+    let (grand, target) = steps.sumcheck.ok_or("sum-check was not reached")?;
+    assert_eq!(grand, target);
+    assert_eq!(steps.shplemini, Some(Ok(())));
+    assert!(steps.transcript.is_some());
+    Ok(())
+}
+
+#[test]
+fn batch_inputs_must_pair_up() {
+    let env = Env::default();
+    env.ledger().set_protocol_version(25);
+    let verifier = UltraHonkVerifier::new_with_vk(&env, zero_vk(4, 16));
+    let proof = Bytes::from_slice(&env, &[0u8; PROOF_BYTES]);
+
+    assert!(matches!(
+        verifier.verify_batch(&[], &[]),
+        Err(VerifyError::InvalidInput("empty batch"))
+    ));
+    assert!(matches!(
+        verifier.verify_batch(std::slice::from_ref(&proof), &[]),
+        Err(VerifyError::InvalidInput("batch length mismatch"))
+    ));
+    assert!(matches!(
+        verifier.verify_each(&[proof.clone(), proof], &[Bytes::new(&env)]),
+        Err((1, VerifyError::InvalidInput("batch length mismatch")))
+    ));
+}
+
+#[test]
+fn flipped_public_input_is_rejected() -> Result<(), String> {
+    match verify_with_flipped_bit(Target::PublicInputs, 0)? {
+        VerifyError::SumcheckFailed(_) => Ok(()),
+        other => Err(format!("expected SumcheckFailed, got {other:?}")),
+    }
+}
+
+/// Feed the exported terms through the host multi-pairing, as an aggregator
+/// batching them with other systems' pairs would.
+fn multi_pairing_is_one(
+    env: &Env,
+    verifier: &UltraHonkVerifier,
+    proof: &Bytes,
+    pis: &Bytes,
+) -> Result<bool, String> {
+    let terms = verifier
+        .verify_pairing_terms(proof, pis)
+        .map_err(|e| format!("{e:?}"))?;
+    let mut g1s = soroban_sdk::Vec::new(env);
+    let mut g2s = soroban_sdk::Vec::new(env);
+    for (g1, g2) in terms {
+        g1s.push_back(g1);
+        g2s.push_back(g2);
+    }
+    Ok(env.crypto().bn254().pairing_check(g1s, g2s))
+}
+
+#[test]
+fn pairing_terms_agree_with_verify() -> Result<(), String> {
+    let env = Env::default();
+    let (verifier, proof, public_inputs) = fixture(&env)?;
+    assert!(verifier.verify(&proof, &public_inputs).is_ok());
+    assert!(multi_pairing_is_one(
+        &env,
+        &verifier,
+        &proof,
+        &public_inputs
+    )?);
+
+    // A wrong generator only breaks the final pairing: both paths must reject.
+    let two_g = G1Point::from_xy(
+        hex32("030644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd3"),
+        hex32("15ed738c0e0a7c92e7845f96b2ae9c0a68a6a449e3538fc7ff3ebf7a5a18a2c4"),
+    );
+    let verifier = verifier.with_generator(two_g);
+    assert!(verifier.verify(&proof, &public_inputs).is_err());
+    assert!(!multi_pairing_is_one(
+        &env,
+        &verifier,
+        &proof,
+        &public_inputs
+    )?);
+    Ok(())
+}
+
+#[test]
+fn public_input_delta_agrees_for_bytes_and_fields() {
+    let env = Env::default();
+    let inputs: Vec<Fr> = (1..=3).map(|i| Fr::from_u64(i * 0x1_0000_0001)).collect();
+    let pairing_point_object = [Fr::from_u64(7), Fr::from_u64(11)];
+    let mut raw = Vec::new();
+    for x in &inputs {
+        raw.extend_from_slice(&x.to_bytes());
+    }
+    let (beta, gamma) = (Fr::from_u64(5), Fr::from_u64(9));
+
+    let from_bytes = UltraHonkVerifier::compute_public_input_delta(
+        &Bytes::from_slice(&env, &raw),
+        &pairing_point_object,
+        beta,
+        gamma,
+        1,
+        16,
+    )
+    .unwrap();
+    let from_fields = UltraHonkVerifier::compute_public_input_delta_fields(
+        inputs.iter().chain(&pairing_point_object).copied(),
+        beta,
+        gamma,
+        1,
+        16,
+    )
+    .unwrap();
+    let from_slice = UltraHonkVerifier::compute_public_input_delta_slice(
+        &raw,
+        &pairing_point_object,
+        beta,
+        gamma,
+        1,
+        16,
+    )
+    .unwrap();
+    assert_eq!(from_bytes, from_fields);
+    assert_eq!(from_slice, from_fields);
+    assert_ne!(from_bytes, Fr::one());
+    assert!(UltraHonkVerifier::compute_public_input_delta_slice(
+        &raw[1..],
+        &pairing_point_object,
+        beta,
+        gamma,
+        1,
+        16
+    )
+    .is_err());
+}
+
+/// One input `x = 5`, `beta = 2`, `gamma = 3`, offset 1, n = 16:
+/// `(γ + β(n + offset) + x) / (γ - β(offset + 1) + x) = 42 / 4`.
+#[test]
+fn public_input_delta_matches_a_hand_computed_value() {
+    let delta = UltraHonkVerifier::compute_public_input_delta_slice(
+        &Fr::from_u64(5).to_bytes(),
+        &[],
+        Fr::from_u64(2),
+        Fr::from_u64(3),
+        1,
+        16,
+    )
+    .unwrap();
+    assert_eq!(delta * Fr::from_u64(4), Fr::from_u64(42));
+
+    // Offset 0 shifts both accumulators: 40 / 6.
+    let delta = UltraHonkVerifier::compute_public_input_delta_slice(
+        &Fr::from_u64(5).to_bytes(),
+        &[],
+        Fr::from_u64(2),
+        Fr::from_u64(3),
+        0,
+        16,
+    )
+    .unwrap();
+    assert_eq!(delta * Fr::from_u64(6), Fr::from_u64(40));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn verify_timed_reports_every_stage() -> Result<(), String> {
+    let env = Env::default();
+    let (verifier, proof, public_inputs) = fixture(&env)?;
+
+    let (result, timings) = verifier.verify_timed(&proof, &public_inputs);
+    result.map_err(|e| format!("{e:?}"))?;
+    for (stage, elapsed) in [
+        ("parse", timings.parse),
+        ("transcript", timings.transcript),
+        ("sumcheck", timings.sumcheck),
+        ("shplemini", timings.shplemini),
+        ("pairing", timings.pairing),
+    ] {
+        if elapsed.is_zero() {
+            return Err(format!("{stage} took no time"));
+        }
+    }
+    Ok(())
+}
+
+/// Run sum-check on the fixture, then hand Shplemini a proof whose entity
+/// evaluation differs from the one sum-check accepted.
+#[test]
+fn evaluations_changed_after_sumcheck_fail_the_opening() -> Result<(), String> {
+    use ultrahonk_soroban_verifier::{
+        shplemini::verify_shplemini, sumcheck::verify_sumcheck, transcript::generate_transcript,
+        types::PAIRING_POINTS_SIZE, utils::load_proof,
+    };
+
+    let env = Env::default();
+    let (verifier, proof_bytes, public_inputs) = fixture(&env)?;
+    let vk = verifier.get_vk();
+
+    let mut proof = load_proof(&proof_bytes).unwrap();
+    let pis_total = (public_inputs.len() / 32) as u64 + PAIRING_POINTS_SIZE as u64;
+    let mut t = generate_transcript(
+        &env,
+        &proof,
+        &public_inputs,
+        vk.circuit_size,
+        pis_total,
+        vk.pub_inputs_offset,
+    );
+    t.rel_params.public_inputs_delta = UltraHonkVerifier::compute_public_input_delta(
+        &public_inputs,
+        &proof.pairing_point_object,
+        t.rel_params.beta,
+        t.rel_params.gamma,
+        vk.pub_inputs_offset,
+        vk.circuit_size,
+    )?;
+    verify_sumcheck(&proof, &t, vk)?;
+    verify_shplemini(&env, &proof, vk, &t, verifier.get_generator())
+        .map_err(|e| e.message().to_string())?;
+
+    proof.sumcheck_evaluations[0] += Fr::one();
+    match verify_shplemini(&env, &proof, vk, &t, verifier.get_generator()) {
+        Err(_) => Ok(()),
+        Ok(()) => Err("a changed evaluation passed the opening".into()),
+    }
+}
+
+/// Wrong public inputs for a valid proof cannot be told apart from a bad
+/// proof; they show up as a sum-check failure after round 0.
+#[test]
+fn wrong_public_inputs_fail_sumcheck_past_round_zero() -> Result<(), String> {
+    let env = Env::default();
+    let (verifier, proof, public_inputs) = fixture(&env)?;
+    let wrong = Bytes::from_slice(&env, &vec![0u8; public_inputs.len() as usize]);
+
+    match verifier.verify(&proof, &wrong) {
+        Err(VerifyError::SumcheckFailed(msg)) if !msg.starts_with("round 0") => Ok(()),
+        other => Err(format!("expected a later sum-check failure, got {other:?}")),
+    }
+}
+
+#[test]
+fn committed_transcript_digest_matches_a_rederived_one() -> Result<(), String> {
+    let env = Env::default();
+    let (verifier, proof, public_inputs) = fixture(&env)?;
+    let committed = verifier
+        .verify_and_commit_transcript(&proof, &public_inputs)
+        .map_err(|e| format!("{e:?}"))?;
+    let t = generate_transcript(
+        &env,
+        &load_proof(&proof).unwrap(),
+        &public_inputs,
+        verifier.get_vk().circuit_size,
+        public_inputs.len() as u64 / 32 + 16,
+        verifier.get_vk().pub_inputs_offset,
+    );
+    assert_eq!(committed, challenge_digest(&env, &t));
+    Ok(())
+}
@@ -0,0 +1,200 @@
+mod common;
+
+use common::{hex32, zero_vk, zero_vk_bytes};
+use soroban_sdk::Env;
+use ultrahonk_soroban_verifier::{
+    types::{BbVersion, G1Point, LagrangeWarning, VerificationKey, VK_NUM_POINTS},
+    utils::{
+        load_vk_from_slice, solidity_vk_encoding, solidity_vk_hash, vk_to_bytes, SOLIDITY_VK_BYTES,
+    },
+    verifier::VerifierManifest,
+    UltraHonkVerifier,
+};
+
+fn point(tag: u8) -> G1Point {
+    let mut x = [0u8; 32];
+    x[31] = tag;
+    G1Point::from_xy(x, [tag; 32])
+}
+
+#[test]
+fn zeroed_lagrange_commitments_are_flagged() {
+    let g = G1Point::generator();
+    let mut vk = zero_vk(4, 18);
+    assert_eq!(
+        vk.lagrange_sanity(&g),
+        Some(LagrangeWarning::FirstIsIdentity)
+    );
+
+    vk.lagrange_first = point(1);
+    assert_eq!(
+        vk.lagrange_sanity(&g),
+        Some(LagrangeWarning::LastIsIdentity)
+    );
+
+    vk.circuit_size = 0;
+    assert_eq!(vk.lagrange_sanity(&g), None);
+}
+
+#[test]
+fn coinciding_or_placeholder_commitments_are_flagged() {
+    let g = G1Point::generator();
+    let mut vk = zero_vk(4, 18);
+    vk.lagrange_first = point(1);
+    vk.lagrange_last = point(1);
+    assert_eq!(
+        vk.lagrange_sanity(&g),
+        Some(LagrangeWarning::FirstEqualsLast)
+    );
+
+    vk.lagrange_last = g;
+    assert_eq!(
+        vk.lagrange_sanity(&g),
+        Some(LagrangeWarning::EqualsGenerator)
+    );
+
+    vk.lagrange_last = point(2);
+    assert_eq!(vk.lagrange_sanity(&g), None);
+}
+
+#[test]
+fn from_points_round_trips_a_parsed_vk() {
+    let mut vk = zero_vk(4, 18);
+    vk.lagrange_first = point(1);
+    vk.t3 = point(7);
+    let rebuilt =
+        VerificationKey::from_points(vk.circuit_size, vk.public_inputs_size, vk.points()).unwrap();
+    assert_eq!(rebuilt, vk);
+}
+
+#[test]
+fn vk_bytes_round_trip_every_field() {
+    let mut raw = zero_vk_bytes(5, 20);
+    raw[31] = 3; // pub_inputs_offset
+    let vk = load_vk_from_slice(&raw).unwrap();
+    let points: [G1Point; VK_NUM_POINTS] = core::array::from_fn(|i| point(i as u8 + 1));
+    let vk = VerificationKey {
+        pub_inputs_offset: vk.pub_inputs_offset,
+        ..VerificationKey::from_points(vk.circuit_size, vk.public_inputs_size, points).unwrap()
+    };
+    assert_eq!(vk.pub_inputs_offset, 3);
+
+    let bytes = vk_to_bytes(&vk);
+    let parsed = load_vk_from_slice(&bytes).unwrap();
+    assert_eq!(parsed, vk);
+    assert_eq!(parsed.points(), points);
+    assert_eq!(vk_to_bytes(&parsed), bytes);
+    assert_eq!(bytes[..32], raw[..32]);
+}
+
+#[test]
+fn from_points_validates_metadata() {
+    let points = [G1Point::infinity(); VK_NUM_POINTS];
+    assert!(VerificationKey::from_points(0, 18, points).is_err());
+    assert!(VerificationKey::from_points(24, 18, points).is_err());
+    assert!(VerificationKey::from_points(1 << 29, 18, points).is_err());
+    assert!(VerificationKey::from_points(16, 15, points).is_err());
+    assert_eq!(
+        VerificationKey::from_points(16, 18, points).map(|vk| vk.log_circuit_size),
+        Ok(4)
+    );
+}
+
+#[test]
+fn verifier_defaults_to_bb_v0_87() {
+    let env = Env::default();
+    let verifier = UltraHonkVerifier::new_with_vk(&env, zero_vk(4, 18));
+    assert_eq!(verifier.get_version(), BbVersion::V0_87);
+
+    let verifier =
+        UltraHonkVerifier::new_with_vk_and_version(&env, zero_vk(4, 18), BbVersion::V0_87);
+    assert_eq!(verifier.get_version(), BbVersion::V0_87);
+    assert_eq!(BbVersion::V0_87.pairing_points_size(), 16);
+    assert_eq!(BbVersion::V0_87.limb_split_bits(), 136);
+}
+
+#[test]
+fn default_manifest_has_the_bb_v0_87_parameters() {
+    let env = Env::default();
+    let verifier = UltraHonkVerifier::new_with_vk(&env, zero_vk(4, 18));
+    let manifest = verifier.manifest();
+    let VerifierManifest {
+        version,
+        const_proof_size_log_n,
+        number_of_entities,
+        number_of_subrelations,
+        pairing_points_size,
+        transcript_hash,
+        limb_split_bits,
+        generator,
+        rhs_g2,
+        lhs_g2,
+    } = manifest.clone();
+    assert_eq!(version, BbVersion::V0_87);
+    assert_eq!(const_proof_size_log_n, 28);
+    assert_eq!(number_of_entities, 40);
+    assert_eq!(number_of_subrelations, 26);
+    assert_eq!(pairing_points_size, 16);
+    assert_eq!(transcript_hash, "keccak256");
+    assert_eq!(limb_split_bits, 136);
+    assert_eq!(generator, G1Point::generator());
+    // The G2 generator's x.c1 limb leads the host encoding.
+    assert_eq!(rhs_g2[..4], [0x19, 0x8e, 0x93, 0x93]);
+    assert_ne!(lhs_g2, rhs_g2);
+
+    let bytes = manifest.to_bytes();
+    assert_eq!(bytes.len(), 5 * 4 + 1 + 9 + 64 + 2 * 128);
+    assert_eq!(bytes[..4], 28u32.to_be_bytes());
+    assert_eq!(&bytes[20..30], b"\x09keccak256");
+
+    // Deployments differing only in the generator are told apart.
+    let other = UltraHonkVerifier::new_with_vk(&env, zero_vk(4, 18)).with_generator(point(1));
+    assert_ne!(other.manifest(), manifest);
+    assert_ne!(other.manifest().to_bytes(), bytes);
+}
+
+#[test]
+fn solidity_encoding_follows_the_struct_layout() {
+    let env = Env::default();
+    let mut vk = zero_vk(4, 18);
+    vk.qm = point(1);
+    vk.q_aux = point(2);
+    vk.q_elliptic = point(3);
+    vk.lagrange_last = point(4);
+    let enc = solidity_vk_encoding(&vk);
+    assert_eq!(enc.len(), SOLIDITY_VK_BYTES);
+
+    let word = |i: usize| &enc[i * 32..(i + 1) * 32];
+    assert_eq!(word(0)[31], 1 << 4);
+    assert_eq!(word(1)[31], 4);
+    assert_eq!(word(2)[31], 18);
+    let pt = |i: usize| &enc[96 + i * 64..96 + (i + 1) * 64];
+    assert_eq!(pt(0), point(1).to_bytes());
+    assert_eq!(pt(9), point(2).to_bytes());
+    assert_eq!(pt(10), point(3).to_bytes());
+    assert_eq!(pt(VK_NUM_POINTS - 1), point(4).to_bytes());
+
+    let expected = env
+        .crypto()
+        .keccak256(&soroban_sdk::Bytes::from_slice(&env, &enc))
+        .to_array();
+    assert_eq!(solidity_vk_hash(&env, &vk), expected);
+}
+
+#[test]
+fn vk_coordinates_must_be_canonical() {
+    let p = hex32("30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd47");
+    let mut raw = zero_vk_bytes(4, 18);
+    // qm.y follows the four metadata words and qm.x.
+    raw[64..96].copy_from_slice(&p);
+    assert!(load_vk_from_slice(&raw).is_none());
+    raw[95] -= 1;
+    assert!(load_vk_from_slice(&raw).is_some());
+
+    let mut points = [G1Point::infinity(); VK_NUM_POINTS];
+    points[VK_NUM_POINTS - 1] = G1Point::from_xy(p, [0u8; 32]);
+    assert_eq!(
+        VerificationKey::from_points(16, 18, points).err(),
+        Some("vk coordinate is not below the base field modulus")
+    );
+}